@@ -0,0 +1,664 @@
+//! Parsing of the `#[builder(...)]` helper attribute.
+
+use crate::Result;
+
+/// The name of the helper attribute used to configure the generated builders.
+const ATTRIBUTE_NAME: &str = "builder";
+
+/// How a field should be initialised if it is never set on the builder.
+pub enum FieldDefault {
+    /// `#[builder(default)]`, the field is initialised with [`Default::default`].
+    Trait,
+    /// `#[builder(default = expr)]`, the field is initialised with the provided expression.
+    Expr(syn::Expr),
+}
+
+/// Options set on a single field through `#[builder(...)]`.
+// Each flag corresponds directly to an option of the attribute.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default)]
+pub struct FieldOptions {
+    pub default: Option<FieldDefault>,
+    /// `#[builder(into)]`, the setter accepts any `impl Into<T>`.
+    pub into: bool,
+    /// `#[builder(strip_option)]`, the setter of an `Option<T>` field accepts `T`.
+    pub strip_option: bool,
+    /// `#[builder(required)]`, the field must be set even if it is an `Option<T>`.
+    pub required: bool,
+    /// `#[builder(optional)]`, the field is optional even if it is not recognised as an
+    /// `Option<T>`, such as a type alias of one.
+    pub optional: bool,
+    /// `#[builder(name = "...")]`, the name used for an unnamed field instead of its position.
+    pub name: Option<syn::Ident>,
+    /// `#[builder(setter(name = "..."))]`, the name of the setter in place of the field name.
+    pub setter_name: Option<syn::Ident>,
+    /// `#[builder(validate = expr)]`, a function checking the value of the field when it is built.
+    pub validate: Option<syn::Expr>,
+    /// `#[builder(each = "...")]`, the name of a setter adding a single item to a collection.
+    pub each: Option<syn::Ident>,
+    /// `#[builder(nested)]`, the setter takes a closure configuring the builder of the field type.
+    pub nested: bool,
+    /// `#[builder(state_alias = "...")]`, the name of an alias for the state in which this field
+    /// is missing.
+    pub state_alias: Option<syn::Ident>,
+    /// `#[builder(doc = "...")]`, the documentation of the setters in place of the doc comments
+    /// of the field.
+    pub doc: Option<syn::LitStr>,
+    /// `#[builder(skip)]` or `#[builder(skip = expr)]`, the field has no setter and is initialised
+    /// when the struct is built.
+    pub skip: Option<FieldDefault>,
+}
+
+impl FieldOptions {
+    /// Parses every `#[builder(...)]` attribute in `attrs`, ignoring any other attributes.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if an option is unknown, malformed or provided more than once.
+    pub fn from_attributes(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut output = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(ATTRIBUTE_NAME))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    set_default(&meta, &mut output.default)
+                } else if meta.path.is_ident("skip") {
+                    set_default(&meta, &mut output.skip)
+                } else if meta.path.is_ident("into") {
+                    set_flag(&meta, &mut output.into)
+                } else if meta.path.is_ident("strip_option") {
+                    set_flag(&meta, &mut output.strip_option)
+                } else if meta.path.is_ident("required") {
+                    set_flag(&meta, &mut output.required)
+                } else if meta.path.is_ident("optional") {
+                    set_flag(&meta, &mut output.optional)
+                } else if meta.path.is_ident("name") {
+                    set_ident(&meta, &mut output.name)
+                } else if meta.path.is_ident("setter") {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("name") {
+                            set_ident(&meta, &mut output.setter_name)
+                        } else {
+                            Err(meta.error("unknown setter option"))
+                        }
+                    })
+                } else if meta.path.is_ident("validate") {
+                    set_expr(&meta, &mut output.validate)
+                } else if meta.path.is_ident("each") {
+                    set_ident(&meta, &mut output.each)
+                } else if meta.path.is_ident("nested") {
+                    set_flag(&meta, &mut output.nested)
+                } else if meta.path.is_ident("state_alias") {
+                    set_ident(&meta, &mut output.state_alias)
+                } else if meta.path.is_ident("doc") {
+                    set_lit(&meta, &mut output.doc)
+                } else {
+                    Err(meta.error("unknown field option"))
+                }
+            })?;
+        }
+        Ok(output)
+    }
+}
+
+/// Options set on the struct deriving `BetterBuilder` through `#[builder(...)]`.
+#[derive(Default)]
+pub struct StructOptions {
+    /// `#[builder(into)]`, every setter accepts any `impl Into<T>` where this does not hinder
+    /// type inference.
+    pub into: bool,
+    /// `#[builder(strip_option)]`, every setter of an `Option<T>` field accepts `T`.
+    pub strip_option: bool,
+    /// `#[builder(unordered)]`, required fields may be set in any order.
+    pub unordered: bool,
+    /// `#[builder(setter_prefix = "...")]`, prepended to the name of every setter which is not
+    /// renamed.
+    pub setter_prefix: Option<syn::LitStr>,
+    /// `#[builder(name = "...")]`, the name of the final builder in place of `{Struct}Builder`.
+    pub name: Option<syn::Ident>,
+    /// `#[builder(constructor = "...")]`, the name of the function creating the builder in place
+    /// of `builder`.
+    pub constructor: Option<syn::Ident>,
+    /// `#[builder(build_fn = "...")]`, the name of the method building the struct in place of
+    /// `build`.
+    pub build_fn: Option<syn::Ident>,
+    /// `#[builder(state_prefix = "...")]`, prepended to the name of each required field to name
+    /// its intermediate state, in place of `{Struct}BuilderMissing`.
+    pub state_prefix: Option<syn::LitStr>,
+    /// `#[builder(validate = expr)]`, a function checking the struct when it is built.
+    pub validate: Option<syn::Expr>,
+    /// `#[builder(derive(...))]`, traits derived on every generated builder.
+    pub derives: Vec<syn::Path>,
+    /// `#[builder(attr(...))]`, attributes added to every generated builder.
+    pub attrs: Vec<syn::Meta>,
+    /// `#[builder(doc = "...")]`, the documentation of the final builder.
+    pub doc: Option<syn::LitStr>,
+    /// `#[builder(crate = "...")]`, the path to `better_builder` used by generated code in place of
+    /// `::better_builder`, for use when it is re-exported by another crate.
+    pub crate_path: Option<syn::Path>,
+}
+
+impl StructOptions {
+    /// Parses every `#[builder(...)]` attribute in `attrs`, ignoring any other attributes.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if an option is unknown, malformed or provided more than once.
+    pub fn from_attributes(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut output = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(ATTRIBUTE_NAME))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("into") {
+                    set_flag(&meta, &mut output.into)
+                } else if meta.path.is_ident("strip_option") {
+                    set_flag(&meta, &mut output.strip_option)
+                } else if meta.path.is_ident("unordered") {
+                    set_flag(&meta, &mut output.unordered)
+                } else if meta.path.is_ident("setter_prefix") {
+                    set_prefix(&meta, &mut output.setter_prefix)
+                } else if meta.path.is_ident("name") {
+                    set_ident(&meta, &mut output.name)
+                } else if meta.path.is_ident("constructor") {
+                    set_ident(&meta, &mut output.constructor)
+                } else if meta.path.is_ident("build_fn") {
+                    set_ident(&meta, &mut output.build_fn)
+                } else if meta.path.is_ident("state_prefix") {
+                    set_prefix(&meta, &mut output.state_prefix)
+                } else if meta.path.is_ident("validate") {
+                    set_expr(&meta, &mut output.validate)
+                } else if meta.path.is_ident("doc") {
+                    set_lit(&meta, &mut output.doc)
+                } else if meta.path.is_ident("crate") {
+                    set_path(&meta, &mut output.crate_path)
+                } else if meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|meta| {
+                        output.derives.push(meta.path);
+                        Ok(())
+                    })
+                } else if meta.path.is_ident("attr") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    output.attrs.push(content.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown struct option"))
+                }
+            })?;
+        }
+        Ok(output)
+    }
+}
+
+/// Fails with a "duplicate option" error naming the option if it was already set.
+fn check_duplicate(meta: &syn::meta::ParseNestedMeta, is_set: bool) -> Result<()> {
+    if is_set {
+        let name = meta
+            .path
+            .get_ident()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        return Err(meta.error(format!("duplicate `{name}` option")));
+    }
+    Ok(())
+}
+
+/// Sets an option such as `default` or `default = expr`, which may take an expression.
+fn set_default(
+    meta: &syn::meta::ParseNestedMeta,
+    default: &mut Option<FieldDefault>,
+) -> Result<()> {
+    check_duplicate(meta, default.is_some())?;
+    *default = Some(if meta.input.peek(syn::Token![=]) {
+        FieldDefault::Expr(meta.value()?.parse()?)
+    } else {
+        FieldDefault::Trait
+    });
+    Ok(())
+}
+
+/// Sets an option such as `doc = "..."`, which takes a string literal.
+fn set_lit(meta: &syn::meta::ParseNestedMeta, lit: &mut Option<syn::LitStr>) -> Result<()> {
+    check_duplicate(meta, lit.is_some())?;
+    *lit = Some(meta.value()?.parse()?);
+    Ok(())
+}
+
+/// Sets an option such as `validate = expr`, which takes any expression.
+fn set_expr(meta: &syn::meta::ParseNestedMeta, expr: &mut Option<syn::Expr>) -> Result<()> {
+    check_duplicate(meta, expr.is_some())?;
+    *expr = Some(meta.value()?.parse()?);
+    Ok(())
+}
+
+/// Sets an option such as `setter_prefix = "..."`, which takes the start of an identifier written as
+/// a string literal.
+fn set_prefix(meta: &syn::meta::ParseNestedMeta, prefix: &mut Option<syn::LitStr>) -> Result<()> {
+    check_duplicate(meta, prefix.is_some())?;
+    let value: syn::LitStr = meta.value()?.parse()?;
+    syn::parse_str::<syn::Ident>(&format!("{}x", value.value()))
+        .map_err(|_| syn::Error::new_spanned(&value, "invalid prefix"))?;
+    *prefix = Some(value);
+    Ok(())
+}
+
+/// Sets an option such as `name = "..."`, which takes an identifier written as a string literal.
+fn set_ident(meta: &syn::meta::ParseNestedMeta, ident: &mut Option<syn::Ident>) -> Result<()> {
+    check_duplicate(meta, ident.is_some())?;
+    let value: syn::LitStr = meta.value()?.parse()?;
+    *ident = Some(value.parse()?);
+    Ok(())
+}
+
+/// Sets an option such as `crate = "..."`, which takes a path written as a string literal.
+fn set_path(meta: &syn::meta::ParseNestedMeta, path: &mut Option<syn::Path>) -> Result<()> {
+    check_duplicate(meta, path.is_some())?;
+    let value: syn::LitStr = meta.value()?.parse()?;
+    *path = Some(value.parse()?);
+    Ok(())
+}
+
+/// Sets a flag option such as `into` or `strip_option`, which takes no value.
+fn set_flag(meta: &syn::meta::ParseNestedMeta, flag: &mut bool) -> Result<()> {
+    check_duplicate(meta, *flag)?;
+    *flag = true;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse::Parser;
+
+    use super::*;
+
+    fn parse_field(tokens: proc_macro2::TokenStream) -> Result<FieldOptions> {
+        let field = syn::Field::parse_named.parse2(tokens)?;
+        FieldOptions::from_attributes(&field.attrs)
+    }
+
+    #[test]
+    fn test_field_options_default() {
+        let field = quote! {
+            #[doc = "Not a builder attribute."]
+            my_field: u8
+        };
+        let options = parse_field(field).unwrap();
+        assert!(options.default.is_none());
+
+        let field = quote! {
+            #[builder(default)]
+            my_field: u8
+        };
+        let options = parse_field(field).unwrap();
+        assert!(matches!(options.default, Some(FieldDefault::Trait)));
+
+        let field = quote! {
+            #[builder(default = 4 + 2)]
+            my_field: u8
+        };
+        let options = parse_field(field).unwrap();
+        assert!(matches!(options.default, Some(FieldDefault::Expr(_))));
+    }
+
+    #[test]
+    fn test_field_options_skip() {
+        let options = parse_field(quote! { my_field: u8 }).unwrap();
+        assert!(options.skip.is_none());
+
+        let field = quote! {
+            #[builder(skip)]
+            my_field: u8
+        };
+        let options = parse_field(field).unwrap();
+        assert!(matches!(options.skip, Some(FieldDefault::Trait)));
+
+        let field = quote! {
+            #[builder(skip = Instant::now())]
+            my_field: Instant
+        };
+        let options = parse_field(field).unwrap();
+        assert!(matches!(options.skip, Some(FieldDefault::Expr(_))));
+
+        let field = quote! {
+            #[builder(skip, skip = 4)]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+    }
+
+    #[test]
+    fn test_field_options_into() {
+        let options = parse_field(quote! { my_field: String }).unwrap();
+        assert!(!options.into);
+
+        let field = quote! {
+            #[builder(into)]
+            my_field: String
+        };
+        let options = parse_field(field).unwrap();
+        assert!(options.into);
+
+        let field = quote! {
+            #[builder(default)]
+            #[builder(into)]
+            my_field: String
+        };
+        let options = parse_field(field).unwrap();
+        assert!(options.into);
+        assert!(options.default.is_some());
+    }
+
+    #[test]
+    fn test_field_options_strip_option() {
+        let options = parse_field(quote! { my_field: Option<u8> }).unwrap();
+        assert!(!options.strip_option);
+
+        let field = quote! {
+            #[builder(strip_option)]
+            my_field: Option<u8>
+        };
+        let options = parse_field(field).unwrap();
+        assert!(options.strip_option);
+    }
+
+    #[test]
+    fn test_field_options_required_optional() {
+        let options = parse_field(quote! { my_field: Option<u8> }).unwrap();
+        assert!(!options.required);
+        assert!(!options.optional);
+
+        let field = quote! {
+            #[builder(required)]
+            my_field: Option<u8>
+        };
+        let options = parse_field(field).unwrap();
+        assert!(options.required);
+
+        let field = quote! {
+            #[builder(optional)]
+            my_field: MaybeU8
+        };
+        let options = parse_field(field).unwrap();
+        assert!(options.optional);
+    }
+
+    #[test]
+    fn test_field_options_state_alias() {
+        let options = parse_field(quote! { owner: String }).unwrap();
+        assert!(options.state_alias.is_none());
+
+        let field = quote! {
+            #[builder(state_alias = "CartNeedsOwner")]
+            owner: String
+        };
+        let options = parse_field(field).unwrap();
+        assert_eq!(options.state_alias.unwrap(), "CartNeedsOwner");
+    }
+
+    #[test]
+    fn test_field_options_doc() {
+        let options = parse_field(quote! { owner: String }).unwrap();
+        assert!(options.doc.is_none());
+
+        let field = quote! {
+            /// The owner of the cart.
+            #[builder(doc = "Sets the owner of the cart.")]
+            owner: String
+        };
+        let options = parse_field(field).unwrap();
+        assert_eq!(options.doc.unwrap().value(), "Sets the owner of the cart.");
+
+        let field = quote! {
+            #[builder(doc = 4)]
+            owner: String
+        };
+        assert!(parse_field(field).is_err());
+    }
+
+    #[test]
+    fn test_field_options_nested() {
+        let options = parse_field(quote! { engine: Engine }).unwrap();
+        assert!(!options.nested);
+
+        let field = quote! {
+            #[builder(nested)]
+            engine: Engine
+        };
+        assert!(parse_field(field).unwrap().nested);
+    }
+
+    #[test]
+    fn test_field_options_name() {
+        let options = parse_field(quote! { my_field: u8 }).unwrap();
+        assert!(options.name.is_none());
+
+        let field = quote! {
+            #[builder(name = "owner")]
+            my_field: u8
+        };
+        let options = parse_field(field).unwrap();
+        assert_eq!(options.name.unwrap(), "owner");
+
+        let field = quote! {
+            #[builder(name = "not an identifier")]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+
+        let field = quote! {
+            #[builder(name = owner)]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+    }
+
+    #[test]
+    fn test_field_options_setter() {
+        let options = parse_field(quote! { my_field: u8 }).unwrap();
+        assert!(options.setter_name.is_none());
+
+        let field = quote! {
+            #[builder(setter(name = "with_field"))]
+            my_field: u8
+        };
+        let options = parse_field(field).unwrap();
+        assert_eq!(options.setter_name.unwrap(), "with_field");
+
+        let field = quote! {
+            #[builder(setter(prefix = "with_"))]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+
+        let field = quote! {
+            #[builder(setter(name = "a"), setter(name = "b"))]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+    }
+
+    #[test]
+    fn test_field_options_validate() {
+        let options = parse_field(quote! { port: u16 }).unwrap();
+        assert!(options.validate.is_none());
+
+        let field = quote! {
+            #[builder(validate = validators::port)]
+            port: u16
+        };
+        let options = parse_field(field).unwrap();
+        assert!(matches!(options.validate, Some(syn::Expr::Path(_))));
+
+        let field = quote! {
+            #[builder(validate = |port: &u16| if *port > 0 { Ok(()) } else { Err("zero") })]
+            port: u16
+        };
+        let options = parse_field(field).unwrap();
+        assert!(matches!(options.validate, Some(syn::Expr::Closure(_))));
+
+        let field = quote! {
+            #[builder(validate)]
+            port: u16
+        };
+        assert!(parse_field(field).is_err());
+    }
+
+    #[test]
+    fn test_field_options_each() {
+        let options = parse_field(quote! { items: Vec<u8> }).unwrap();
+        assert!(options.each.is_none());
+
+        let field = quote! {
+            #[builder(each = "item")]
+            items: Vec<u8>
+        };
+        let options = parse_field(field).unwrap();
+        assert_eq!(options.each.unwrap(), "item");
+
+        let field = quote! {
+            #[builder(each = "item", each = "other")]
+            items: Vec<u8>
+        };
+        assert!(parse_field(field).is_err());
+    }
+
+    #[test]
+    fn test_struct_options() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(into)]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert!(options.into);
+        assert!(!options.strip_option);
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(strip_option, into)]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert!(options.into);
+        assert!(options.strip_option);
+        assert!(!options.unordered);
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(unordered)]
+            struct MyStruct {}
+        };
+        assert!(
+            StructOptions::from_attributes(&input.attrs)
+                .unwrap()
+                .unordered
+        );
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(setter_prefix = "with_")]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert_eq!(options.setter_prefix.unwrap().value(), "with_");
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(
+                name = "CartFactory",
+                constructor = "factory",
+                build_fn = "finish",
+                state_prefix = "CartNeeds",
+            )]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert_eq!(options.name.unwrap(), "CartFactory");
+        assert_eq!(options.constructor.unwrap(), "factory");
+        assert_eq!(options.build_fn.unwrap(), "finish");
+        assert_eq!(options.state_prefix.unwrap().value(), "CartNeeds");
+        assert!(options.validate.is_none());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(validate = Self::validate)]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert!(options.validate.is_some());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(doc = "Builds a `MyStruct`.")]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert_eq!(options.doc.unwrap().value(), "Builds a `MyStruct`.");
+        assert!(options.crate_path.is_none());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(crate = "my_crate::builder")]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        let crate_path = options.crate_path.unwrap();
+        assert_eq!(quote! { #crate_path }.to_string(), "my_crate :: builder");
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(crate = "not a path")]
+            struct MyStruct {}
+        };
+        assert!(StructOptions::from_attributes(&input.attrs).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(derive(Clone, std::fmt::Debug), attr(must_use))]
+            #[builder(derive(PartialEq), attr(doc = "A builder."))]
+            struct MyStruct {}
+        };
+        let options = StructOptions::from_attributes(&input.attrs).unwrap();
+        assert_eq!(options.derives.len(), 3);
+        assert_eq!(options.attrs.len(), 2);
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(derive(Clone = true))]
+            struct MyStruct {}
+        };
+        assert!(StructOptions::from_attributes(&input.attrs).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(state_prefix = "0Cart")]
+            struct MyStruct {}
+        };
+        assert!(StructOptions::from_attributes(&input.attrs).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(setter_prefix = "with-")]
+            struct MyStruct {}
+        };
+        assert!(StructOptions::from_attributes(&input.attrs).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(into, into)]
+            struct MyStruct {}
+        };
+        assert!(StructOptions::from_attributes(&input.attrs).is_err());
+    }
+
+    #[test]
+    fn test_field_options_errors() {
+        let field = quote! {
+            #[builder(default, default = 4)]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+
+        let field = quote! {
+            #[builder(not_an_option)]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+
+        let field = quote! {
+            #[builder(into = true)]
+            my_field: u8
+        };
+        assert!(parse_field(field).is_err());
+    }
+}
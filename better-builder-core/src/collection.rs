@@ -0,0 +1,121 @@
+//! Code generation for `#[builder(each = "...")]`, where a collection is built up one item at a
+//! time.
+//!
+//! Fields with an `each` setter start out as an empty collection, so they are set on the final
+//! builder alongside the optional fields. Items are added through [`Extend`], which every
+//! recognised collection implements.
+
+use quote::quote;
+use syn::ext::IdentExt;
+
+use crate::{attributes::FieldDefault, FieldData};
+
+/// The items held by a collection recognised by `#[builder(each = "...")]`.
+pub enum ItemType<'a> {
+    /// A sequence or set of values, such as `Vec<T>` or `HashSet<T>`.
+    Value(&'a syn::Type),
+    /// A map of keys to values, such as `HashMap<K, V>`.
+    Entry(&'a syn::Type, &'a syn::Type),
+}
+
+impl<'a> FieldData<'a> {
+    /// Extracts the item types of a field holding a standard collection.
+    ///
+    /// The collection is recognised by the last segment of its path, so it may be qualified, such
+    /// as `std::collections::HashMap<K, V>`. Additional arguments, such as the hasher of a
+    /// `HashMap`, are ignored.
+    pub fn collection_item_type(&self) -> Option<ItemType<'a>> {
+        const VALUE_COLLECTIONS: &[&str] = &[
+            "Vec",
+            "VecDeque",
+            "LinkedList",
+            "BinaryHeap",
+            "HashSet",
+            "BTreeSet",
+        ];
+        const ENTRY_COLLECTIONS: &[&str] = &["HashMap", "BTreeMap"];
+
+        let syn::Type::Path(syn::TypePath { qself: None, path }) = self.ty else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+        let mut types = arguments.args.iter().filter_map(|argument| match argument {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        });
+
+        if VALUE_COLLECTIONS.iter().any(|name| segment.ident == name) {
+            types.next().map(ItemType::Value)
+        } else if ENTRY_COLLECTIONS.iter().any(|name| segment.ident == name) {
+            Some(ItemType::Entry(types.next()?, types.next()?))
+        } else {
+            None
+        }
+    }
+
+    /// The names of the setter adding a single item and the setter adding many items, if the
+    /// field has `#[builder(each = "...")]`.
+    pub fn each_names(&self) -> Option<(syn::Ident, syn::Ident)> {
+        let each = self.options.each.clone()?;
+        let extend = quote::format_ident!("extend_{}", self.ident.unraw(), span = each.span());
+        Some((each, extend))
+    }
+
+    /// Generates the setters adding items to the collection, for use on a builder holding the
+    /// field.
+    pub fn each_setters(&self) -> Vec<proc_macro2::TokenStream> {
+        let (Some((each, extend)), Some(item)) = (self.each_names(), self.collection_item_type()) else {
+            return Vec::new();
+        };
+        let field_name = &self.ident;
+        let init = if let Some(FieldDefault::Expr(expr)) = &self.options.default {
+            quote! { || #expr }
+        } else {
+            quote! { ::core::default::Default::default }
+        };
+
+        let (item_type, arguments, item) = match item {
+            ItemType::Value(ty) => (quote! { #ty }, quote! { #each: #ty }, quote! { #each }),
+            ItemType::Entry(key, value) => (
+                quote! { (#key, #value) },
+                quote! { key: #key, value: #value },
+                quote! { (key, value) },
+            ),
+        };
+
+        let each_doc = format!(" Adds a single item to `{}`.", field_name.unraw());
+        let extend_doc = format!(
+            " Adds every item of an iterator to `{}`.",
+            field_name.unraw()
+        );
+
+        vec![
+            quote! {
+                #[doc = #each_doc]
+                pub fn #each(mut self, #arguments) -> Self {
+                    ::core::iter::Extend::extend(
+                        self.#field_name.get_or_insert_with(#init),
+                        ::core::iter::once(#item),
+                    );
+                    self
+                }
+            },
+            quote! {
+                #[doc = #extend_doc]
+                pub fn #extend(
+                    mut self,
+                    #field_name: impl ::core::iter::IntoIterator<Item = #item_type>,
+                ) -> Self {
+                    ::core::iter::Extend::extend(
+                        self.#field_name.get_or_insert_with(#init),
+                        #field_name,
+                    );
+                    self
+                }
+            },
+        ]
+    }
+}
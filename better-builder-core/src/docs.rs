@@ -0,0 +1,178 @@
+//! Generation of the documentation attached to builders and their methods.
+//!
+//! Setters forward the doc comments of their field, unless overridden with
+//! `#[builder(doc = "...")]`. Builders describe which fields remain to be set.
+
+use quote::quote;
+use syn::ext::IdentExt;
+
+use crate::{BetterBuilderGenerator, FieldData};
+
+/// Formats a list of field names for use in a sentence, such as "`a`, `b` and `c`".
+fn list_fields<'b>(fields: impl IntoIterator<Item = &'b FieldData<'b>>) -> String {
+    let names = fields
+        .into_iter()
+        .map(|field| format!("`{}`", field.ident.unraw()))
+        .collect::<Vec<_>>();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+/// Generates `#[doc = ...]` attributes for each paragraph of `docs`.
+fn doc_attributes(docs: &[String]) -> proc_macro2::TokenStream {
+    let lines = docs.iter().enumerate().map(|(index, paragraph)| {
+        let separator = (index > 0).then(|| quote! { #[doc = ""] });
+        let paragraph = format!(" {paragraph}");
+        quote! {
+            #separator
+            #[doc = #paragraph]
+        }
+    });
+    quote! { #(#lines)* }
+}
+
+impl<'a> FieldData<'a> {
+    /// The documentation of the setters of this field, which is `#[builder(doc = "...")]` if set,
+    /// otherwise the doc comments of the field itself.
+    pub fn setter_docs(&self) -> proc_macro2::TokenStream {
+        if let Some(doc) = &self.options.doc {
+            return quote! { #[doc = #doc] };
+        }
+
+        let docs = self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+        if docs.is_empty() {
+            doc_attributes(&[format!("Sets `{}`.", self.ident.unraw())])
+        } else {
+            quote! { #(#docs)* }
+        }
+    }
+}
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// A link to the struct or variant being built, such as ``[`Message::Ping`]``, or to the
+    /// function being called, such as ``[`connect()`]``.
+    pub fn target_link(&self) -> String {
+        if let Some(function) = &self.function {
+            return format!("[`{}()`]", function.doc_name());
+        }
+        let struct_name = self.struct_name();
+        self.variant.map_or_else(
+            || format!("[`{struct_name}`]"),
+            |variant| format!("[`{struct_name}::{}`]", variant.ident.unraw()),
+        )
+    }
+
+    /// The paragraph listing the optional fields, if there are any.
+    fn optional_fields_doc(&self) -> Option<String> {
+        let optional = self.fields.iter().filter(|field| !field.is_required());
+        let optional = list_fields(optional);
+        (!optional.is_empty()).then(|| format!("The optional fields {optional} may also be set."))
+    }
+
+    /// Generates the documentation of the intermediate state of the chain of builders in which
+    /// the required field at `index` is set next.
+    pub fn state_docs(&self, index: usize) -> proc_macro2::TokenStream {
+        let field = &self.fields[index];
+        let mut docs = vec![format!(
+            "A builder for {}, on which `{}` must be set next.",
+            self.target_link(),
+            field.ident.unraw()
+        )];
+        let remaining = list_fields(
+            self.fields
+                .iter()
+                .skip(index + 1)
+                .filter(|field| field.is_required()),
+        );
+        if !remaining.is_empty() {
+            docs.push(format!("It is followed by {remaining}."));
+        }
+        docs.extend(self.optional_fields_doc());
+        doc_attributes(&docs)
+    }
+
+    /// Generates the documentation of the final builder, on which every required field is set.
+    pub fn final_builder_docs(&self) -> proc_macro2::TokenStream {
+        if let Some(doc) = &self.options.doc {
+            return quote! { #[doc = #doc] };
+        }
+
+        let mut docs = vec![format!(
+            "A builder for {}, on which every required field has been set.",
+            self.target_link(),
+        )];
+        docs.extend(self.optional_fields_doc());
+        doc_attributes(&docs)
+    }
+
+    /// Generates the documentation of the builder used by `#[builder(unordered)]`.
+    pub fn unordered_builder_docs(&self) -> proc_macro2::TokenStream {
+        if let Some(doc) = &self.options.doc {
+            return quote! { #[doc = #doc] };
+        }
+
+        let required = list_fields(self.fields.iter().filter(|field| field.is_required()));
+        let mut docs = vec![format!("A builder for {}.", self.target_link())];
+        if !required.is_empty() {
+            docs.push(format!(
+                "The required fields {required} may be set in any order, and must all be set \
+                 before it is built."
+            ));
+        }
+        docs.extend(self.optional_fields_doc());
+        doc_attributes(&docs)
+    }
+
+    /// Generates the documentation of the function creating the builder.
+    pub fn entry_point_docs(&self) -> proc_macro2::TokenStream {
+        doc_attributes(&[format!("Creates a builder for {}.", self.target_link())])
+    }
+
+    /// Generates the documentation of the method building the struct.
+    pub fn build_fn_docs(&self) -> proc_macro2::TokenStream {
+        let doc = if self.function.is_some() {
+            format!(
+                "Calls {} with the arguments set on the builder.",
+                self.target_link()
+            )
+        } else if self.is_validated() {
+            format!(
+                "Builds the {}, returning an error if it fails validation.",
+                self.target_link()
+            )
+        } else {
+            format!("Builds the {}.", self.target_link())
+        };
+        doc_attributes(&[doc])
+    }
+
+    /// Generates the documentation of `to_builder()`.
+    pub fn to_builder_docs(&self) -> proc_macro2::TokenStream {
+        doc_attributes(&[format!(
+            "Turns the {} back into a builder on which every field is set, so it can be modified \
+             and built again.",
+            self.target_link()
+        )])
+    }
+
+    /// Generates the documentation of the error returned when validation fails.
+    pub fn error_docs(&self) -> proc_macro2::TokenStream {
+        doc_attributes(&[format!(
+            "The error returned when building a {} fails validation.",
+            self.target_link()
+        )])
+    }
+
+    /// Generates the documentation of the unit struct marking a field as missing in unordered
+    /// mode.
+    pub fn missing_marker_docs(field: &FieldData) -> proc_macro2::TokenStream {
+        doc_attributes(&[format!("Marks `{}` as not yet set.", field.ident.unraw())])
+    }
+}
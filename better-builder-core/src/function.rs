@@ -0,0 +1,818 @@
+//! Code generation for `#[better_builder::builder]`, where the arguments of a function are set
+//! through a builder whose `call()` invokes the function.
+//!
+//! The arguments are turned into the fields of a struct which is never emitted, so the builders
+//! are generated exactly as they are for a derive. Builders cannot elide lifetimes, so elided
+//! lifetimes of the arguments are named. Methods carry their receiver through every builder until
+//! they are called.
+
+use quote::quote;
+use syn::{ext::IdentExt, spanned::Spanned, visit_mut::VisitMut};
+
+use crate::{
+    attributes::StructOptions, convert_snake_case_to_upper_camel_case, BetterBuilderGenerator,
+    FieldData, Result,
+};
+
+/// The name of the field holding the receiver of a method on every builder.
+const RECEIVER_FIELD: &str = "__better_builder_receiver";
+
+/// The receiver of a method, such as `&self`.
+struct Receiver {
+    /// The type stored on the builders, such as `&'__better_builder_0 Client`.
+    ty: syn::Type,
+    /// The receiver as declared by the entry point, such as `&'__better_builder_0 self`.
+    declaration: proc_macro2::TokenStream,
+}
+
+/// The function called by a builder generated with `#[better_builder::builder]`.
+pub struct Callee {
+    /// The name of the function, such as `connect`.
+    ident: syn::Ident,
+    /// The type implementing the function, if it is a method.
+    self_ty: Option<syn::Type>,
+    /// The generics of the `impl` block of a method, which also hold its entry point.
+    impl_generics: syn::Generics,
+    /// The generics declared by the entry point, which are those of the function along with any
+    /// named lifetimes.
+    entry_generics: syn::Generics,
+    receiver: Option<Receiver>,
+    /// The names of the arguments, in the order they are declared.
+    arguments: Vec<syn::Ident>,
+    output: syn::ReturnType,
+    asyncness: Option<syn::Token![async]>,
+    unsafety: Option<syn::Token![unsafe]>,
+    /// Whether the function is marked with `#[better_builder::constructor]`, so it is built like
+    /// the type it constructs.
+    constructor: bool,
+}
+
+impl Callee {
+    /// The name of the function creating the builder, such as `connect_builder`, or `builder` for
+    /// a constructor.
+    pub fn entry_point_name(&self) -> syn::Ident {
+        if self.constructor {
+            return syn::Ident::new("builder", proc_macro2::Span::call_site());
+        }
+        quote::format_ident!("{}_builder", self.ident.unraw())
+    }
+
+    /// The name of the method calling the function, which is `call`, or `build` for a
+    /// constructor.
+    pub const fn build_fn_name(&self) -> &'static str {
+        if self.constructor {
+            "build"
+        } else {
+            "call"
+        }
+    }
+
+    /// The type returned by the method calling the function, unless the function is `async` or
+    /// `unsafe`, which cannot be called through a trait, or returns an `impl Trait`, which cannot
+    /// be named by an associated type.
+    pub fn output_type(&self) -> Option<proc_macro2::TokenStream> {
+        if self.asyncness.is_some() || self.unsafety.is_some() {
+            return None;
+        }
+        match &self.output {
+            syn::ReturnType::Default => Some(quote! { () }),
+            syn::ReturnType::Type(_, ty) => {
+                let mut impl_trait = FindImplTrait(None);
+                impl_trait.visit_type_mut(&mut ty.as_ref().clone());
+                impl_trait.0.is_none().then(|| quote! { #ty })
+            }
+        }
+    }
+
+    /// The name of the function for use in documentation, such as `Client::connect`.
+    pub fn doc_name(&self) -> String {
+        self.self_ty.as_ref().and_then(type_ident).map_or_else(
+            || self.ident.unraw().to_string(),
+            |self_ident| format!("{}::{}", self_ident.unraw(), self.ident.unraw()),
+        )
+    }
+
+    /// The path calling the function, including its type and const parameters, such as
+    /// `<Client>::connect::<T>`.
+    fn path(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        // Lifetimes may be late bound, so they are left to be inferred.
+        let params = self
+            .entry_generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Type(param) => Some(&param.ident),
+                syn::GenericParam::Const(param) => Some(&param.ident),
+                syn::GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let turbofish = (!params.is_empty()).then(|| quote! { ::<#(#params),*> });
+
+        self.self_ty.as_ref().map_or_else(
+            || quote! { #ident #turbofish },
+            |self_ty| quote! { <#self_ty>::#ident #turbofish },
+        )
+    }
+}
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// Generates the declaration of the field holding the receiver of a method, and its
+    /// initialiser when moving from one builder to the next. Both are empty for anything but a
+    /// method taking `self`.
+    pub fn generate_receiver_field(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let Some(receiver) = self.function.as_ref().and_then(|function| function.receiver.as_ref())
+        else {
+            return (quote! {}, quote! {});
+        };
+
+        let field = syn::Ident::new(RECEIVER_FIELD, proc_macro2::Span::call_site());
+        let ty = &receiver.ty;
+        (quote! { #field: #ty, }, quote! { #field: self.#field, })
+    }
+
+    /// Generates the function creating the builder for a function, named `name` with generic
+    /// `arguments`, whose fields other than the marker and receiver are initialised with
+    /// `fields_init`.
+    ///
+    /// The entry point of a method is an associated function of the same type, while the entry
+    /// point of a free function is a free function next to it, which is returned apart as it can be
+    /// named. It creates its first builder through the `complete` builder.
+    pub fn generate_function_entry_point(
+        &self,
+        function: &Callee,
+        name: &proc_macro2::TokenStream,
+        arguments: &proc_macro2::TokenStream,
+        fields_init: &proc_macro2::TokenStream,
+        complete: &proc_macro2::TokenStream,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let entry_point_name = self.entry_point_name();
+        let visibility = self.visibility();
+        let docs = self.entry_point_docs();
+        let (_, marker_init) = self.generate_marker_field();
+        let receiver = function
+            .receiver
+            .as_ref()
+            .map(|receiver| &receiver.declaration);
+        let receiver_init = function.receiver.as_ref().map(|_| {
+            let field = syn::Ident::new(RECEIVER_FIELD, proc_macro2::Span::call_site());
+            quote! { #field: self, }
+        });
+
+        // A free function is declared outside of the anonymous constant, so it cannot name the first
+        // state, and creates it through the complete builder instead.
+        let Some(self_ty) = &function.self_ty else {
+            let crate_path = self.crate_path();
+            let (impl_generics, _, where_clause) = self.generics().split_for_impl();
+            let start = quote! { <#complete as #crate_path::__private::Start> };
+            let exposed = quote! {
+                #docs
+                #visibility fn #entry_point_name #impl_generics() -> #start::Start #where_clause {
+                    #start::start()
+                }
+            };
+            let hidden = quote! {
+                impl #impl_generics #crate_path::__private::Start for #complete #where_clause {
+                    type Start = #name #arguments;
+
+                    fn start() -> Self::Start {
+                        #name {
+                            #fields_init
+                            #marker_init
+                        }
+                    }
+                }
+            };
+            return (exposed, hidden);
+        };
+
+        let (impl_generics, _, impl_where_clause) = function.impl_generics.split_for_impl();
+        let (entry_generics, _, entry_where_clause) = function.entry_generics.split_for_impl();
+        let entry_point = quote! {
+            impl #impl_generics #self_ty #impl_where_clause {
+                #docs
+                #visibility fn #entry_point_name #entry_generics(
+                    #receiver
+                ) -> #name #arguments #entry_where_clause {
+                    #name {
+                        #fields_init
+                        #receiver_init
+                        #marker_init
+                    }
+                }
+            }
+        };
+        (quote! {}, entry_point)
+    }
+
+    /// Generates the method calling the function, where `values` holds the expression taking
+    /// each field out of the builder.
+    ///
+    /// Asynchronous functions are called by an asynchronous method, so `call()` returns the future
+    /// of the function.
+    pub fn generate_call_fn(
+        &self,
+        function: &Callee,
+        values: &[proc_macro2::TokenStream],
+    ) -> proc_macro2::TokenStream {
+        let build_fn_name = self.build_fn_name();
+        let docs = self.build_fn_docs();
+        let path = function.path();
+        let output = &function.output;
+        let asyncness = &function.asyncness;
+        let unsafety = &function.unsafety;
+
+        let receiver = function.receiver.as_ref().map(|_| {
+            let field = syn::Ident::new(RECEIVER_FIELD, proc_macro2::Span::call_site());
+            quote! { self.#field, }
+        });
+        let arguments = function.arguments.iter().map(|argument| {
+            let index = self
+                .fields
+                .iter()
+                .position(|field| field.ident == *argument);
+            index.map_or_else(
+                || {
+                    self.skipped
+                        .iter()
+                        .find(|field| field.ident == *argument)
+                        .map(FieldData::skip_value)
+                        .expect("Every argument is either a field or skipped")
+                },
+                |index| values[index].clone(),
+            )
+        });
+
+        let mut call = quote! { #path(#receiver #(#arguments),*) };
+        if asyncness.is_some() {
+            call = quote! { #call.await };
+        }
+        if unsafety.is_some() {
+            call = quote! { unsafe { #call } };
+        }
+
+        quote! {
+            #docs
+            #[allow(unused_unsafe)]
+            pub #asyncness #unsafety fn #build_fn_name(self) #output {
+                #call
+            }
+        }
+    }
+}
+
+/// Expands `#[better_builder::builder]` on a function, or on an `impl` block in which methods are
+/// marked with `#[builder]`.
+///
+/// ## Errors
+///
+/// Returns an error if the attribute is used on anything else, or if a function cannot have a
+/// builder.
+pub fn expand(args: proc_macro2::TokenStream, item: syn::Item) -> Result<proc_macro2::TokenStream> {
+    match item {
+        syn::Item::Fn(mut item_fn) => {
+            let options = parse_options(&args)?;
+            let builder = expand_function(options, &item_fn.vis, &mut item_fn.sig, None, false)?;
+            Ok(quote! {
+                #item_fn
+                #builder
+            })
+        }
+        syn::Item::Impl(mut item_impl) => {
+            if !args.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    args,
+                    "options are set on each method with `#[builder(...)]`",
+                ));
+            }
+            if let Some((_, path, _)) = &item_impl.trait_ {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "`#[better_builder::builder]` can only be used on inherent `impl` blocks",
+                ));
+            }
+
+            let implementation = (item_impl.generics.clone(), (*item_impl.self_ty).clone());
+            let mut builders = Vec::new();
+            let mut has_constructor = false;
+            for item in &mut item_impl.items {
+                let syn::ImplItem::Fn(method) = item else {
+                    continue;
+                };
+                let Some(index) = method
+                    .attrs
+                    .iter()
+                    .position(|attr| attr.path().is_ident("builder") || is_constructor(attr))
+                else {
+                    continue;
+                };
+                let attr = method.attrs.remove(index);
+                let constructor = is_constructor(&attr);
+                if constructor && std::mem::replace(&mut has_constructor, true) {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "only one constructor of a type can have a builder",
+                    ));
+                }
+
+                let options = match &attr.meta {
+                    syn::Meta::Path(_) => StructOptions::default(),
+                    syn::Meta::List(list) => parse_options(&list.tokens)?,
+                    syn::Meta::NameValue(_) => {
+                        return Err(syn::Error::new_spanned(attr, "expected `#[builder(...)]`"));
+                    }
+                };
+                builders.push(expand_function(
+                    options,
+                    &method.vis,
+                    &mut method.sig,
+                    Some(&implementation),
+                    constructor,
+                )?);
+            }
+
+            Ok(quote! {
+                #item_impl
+                #(#builders)*
+            })
+        }
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`#[better_builder::builder]` can only be used on functions and `impl` blocks",
+        )),
+    }
+}
+
+/// Expands `#[better_builder::constructor]` outside of an `impl` block with
+/// `#[better_builder::builder]`, which would otherwise have removed it.
+///
+/// The builder cannot be generated from the function alone, so an error is reported next to the
+/// unchanged function.
+pub fn expand_constructor(item: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let error = syn::Error::new_spanned(
+        item,
+        "`#[better_builder::constructor]` can only be used on an associated function of an `impl` \
+         block with `#[better_builder::builder]`",
+    )
+    .to_compile_error();
+    quote! {
+        #error
+        #item
+    }
+}
+
+/// Checks if an attribute is `#[better_builder::constructor]`, which may have been imported.
+fn is_constructor(attr: &syn::Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .map_or(false, |segment| segment.ident == "constructor")
+}
+
+/// Parses the options of a function, given as the arguments of its attribute.
+fn parse_options(args: &proc_macro2::TokenStream) -> Result<StructOptions> {
+    if args.is_empty() {
+        return Ok(StructOptions::default());
+    }
+    let attr: syn::Attribute = syn::parse_quote! { #[builder(#args)] };
+    StructOptions::from_attributes(&[attr])
+}
+
+/// Generates the builders of a single function, removing the `#[builder(...)]` and doc
+/// attributes from its arguments.
+///
+/// `implementation` holds the generics and type of the `impl` block of a method. The builders of
+/// a `constructor` are named after the type, as they would be for a derive.
+fn expand_function(
+    options: StructOptions,
+    visibility: &syn::Visibility,
+    sig: &mut syn::Signature,
+    implementation: Option<&(syn::Generics, syn::Type)>,
+    constructor: bool,
+) -> Result<proc_macro2::TokenStream> {
+    if let Some(validate) = &options.validate {
+        return Err(syn::Error::new_spanned(
+            validate,
+            "`validate` is not supported on functions",
+        ));
+    }
+    if let Some(variadic) = &sig.variadic {
+        return Err(syn::Error::new_spanned(
+            variadic,
+            "variadic functions cannot have a builder",
+        ));
+    }
+
+    let self_ty = implementation.map(|(_, self_ty)| self_ty);
+    let target_name = target_name(sig, self_ty, constructor)?;
+
+    let Inputs {
+        fields,
+        names,
+        receiver,
+        lifetimes,
+        output_lifetime,
+    } = Inputs::parse(sig, self_ty)?;
+    if let (true, Some(receiver)) = (constructor, &receiver) {
+        return Err(syn::Error::new_spanned(
+            &receiver.declaration,
+            "constructors cannot take `self`",
+        ));
+    }
+    let mut output = sig.output.clone();
+    if let Some(lifetime) = &output_lifetime {
+        Lifetimes::Assign(lifetime).visit_return_type_mut(&mut output);
+    }
+    if let Some(self_ty) = self_ty {
+        ReplaceSelf(self_ty).visit_return_type_mut(&mut output);
+    }
+
+    let entry_generics = entry_generics(&sig.generics, lifetimes);
+    let impl_generics =
+        implementation.map_or_else(syn::Generics::default, |(generics, _)| generics.clone());
+    let mut generics = merge_generics(&impl_generics, &entry_generics);
+    if let Some(self_ty) = self_ty {
+        ReplaceSelf(self_ty).visit_generics_mut(&mut generics);
+    }
+
+    let input = syn::DeriveInput {
+        attrs: Vec::new(),
+        vis: visibility.clone(),
+        ident: syn::Ident::new(&target_name, sig.ident.span()),
+        generics,
+        data: syn::Data::Struct(syn::DataStruct {
+            struct_token: syn::Token![struct](sig.fn_token.span),
+            fields: syn::Fields::Named(syn::FieldsNamed {
+                brace_token: syn::token::Brace::default(),
+                named: fields,
+            }),
+            semi_token: None,
+        }),
+    };
+    let syn::Data::Struct(data) = &input.data else {
+        unreachable!("The input is always a struct");
+    };
+
+    let mut generator = BetterBuilderGenerator::new(&input, None, options, &data.fields)?;
+    if let Some(field) = generator
+        .fields
+        .iter()
+        .find(|field| field.options.validate.is_some())
+    {
+        return Err(syn::Error::new_spanned(
+            &field.options.validate,
+            "`validate` is not supported on functions",
+        ));
+    }
+    if let Some(self_ty) = self_ty {
+        generator.replace_self(self_ty);
+    }
+    generator.function = Some(Callee {
+        ident: sig.ident.clone(),
+        self_ty: self_ty.cloned(),
+        impl_generics,
+        entry_generics,
+        receiver,
+        arguments: names,
+        output,
+        asyncness: sig.asyncness,
+        unsafety: sig.unsafety,
+        constructor,
+    });
+    Ok(generator.generate())
+}
+
+/// The arguments of a function, turned into the fields of the struct its builders are generated
+/// for.
+struct Inputs {
+    fields: syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    /// The names of the arguments, in the order they are declared.
+    names: Vec<syn::Ident>,
+    receiver: Option<Receiver>,
+    /// The lifetimes naming the elided lifetimes of the arguments.
+    lifetimes: Vec<syn::Lifetime>,
+    /// The lifetime of elided lifetimes in the output, if the elision rules give one.
+    output_lifetime: Option<syn::Lifetime>,
+}
+
+impl Inputs {
+    /// Parses the arguments of `sig`, removing the `#[builder(...)]` and doc attributes which
+    /// belong to their setters. `self_ty` is the type implementing a method.
+    fn parse(sig: &mut syn::Signature, self_ty: Option<&syn::Type>) -> Result<Self> {
+        let mut inputs = Self {
+            fields: syn::punctuated::Punctuated::new(),
+            names: Vec::new(),
+            receiver: None,
+            lifetimes: Vec::new(),
+            output_lifetime: None,
+        };
+        for input in &mut sig.inputs {
+            match input {
+                syn::FnArg::Receiver(input) => {
+                    let Some(self_ty) = self_ty else {
+                        return Err(syn::Error::new_spanned(
+                            input,
+                            "methods must be in an `impl` block with `#[better_builder::builder]`, \
+                             and marked with `#[builder]`",
+                        ));
+                    };
+                    inputs.receiver = Some(inputs.parse_receiver(input, self_ty));
+                }
+                syn::FnArg::Typed(input) => {
+                    let field = inputs.parse_argument(input, self_ty)?;
+                    inputs.fields.push(field);
+                }
+            }
+        }
+
+        // Elided lifetimes of the output follow the elision rules of the function: the lifetime
+        // of a `&self` receiver, otherwise the only lifetime of the arguments.
+        if inputs.output_lifetime.is_none() {
+            let mut named = Vec::new();
+            let mut collect = Lifetimes::Collect(&mut named);
+            for field in &mut inputs.fields {
+                collect.visit_type_mut(&mut field.ty);
+            }
+            if named.len() == 1 {
+                inputs.output_lifetime = named.pop();
+            }
+        }
+        Ok(inputs)
+    }
+
+    /// Parses the receiver of a method, naming its lifetime if it is elided.
+    fn parse_receiver(&mut self, input: &syn::Receiver, self_ty: &syn::Type) -> Receiver {
+        let mut ty = (*input.ty).clone();
+        Lifetimes::Create(&mut self.lifetimes).visit_type_mut(&mut ty);
+        let declaration = match (&input.colon_token, &ty) {
+            (None, syn::Type::Reference(reference)) => {
+                self.output_lifetime = reference.lifetime.clone();
+                let lifetime = &reference.lifetime;
+                let mutability = &reference.mutability;
+                quote! { &#lifetime #mutability self }
+            }
+            (None, _) => quote! { self },
+            (Some(_), _) => {
+                // `self: &Self` takes part in lifetime elision like `&self`.
+                if let syn::Type::Reference(reference) = &ty {
+                    self.output_lifetime = reference.lifetime.clone();
+                }
+                quote! { self: #ty }
+            }
+        };
+        ReplaceSelf(self_ty).visit_type_mut(&mut ty);
+        Receiver { ty, declaration }
+    }
+
+    /// Parses a typed argument into a field, moving the attributes of its setter onto the field.
+    fn parse_argument(
+        &mut self,
+        input: &mut syn::PatType,
+        self_ty: Option<&syn::Type>,
+    ) -> Result<syn::Field> {
+        let ident = match &*input.pat {
+            syn::Pat::Ident(syn::PatIdent {
+                by_ref: None,
+                subpat: None,
+                ident,
+                ..
+            }) => ident.clone(),
+            pat => {
+                return Err(syn::Error::new_spanned(
+                    pat,
+                    "arguments must be named to have a setter, such as `host: String`",
+                ));
+            }
+        };
+
+        // Attribute macros run before `cfg` is evaluated on their input, so a disabled argument
+        // would still get a setter.
+        if let Some(attr) = input
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("cfg") || attr.path().is_ident("cfg_attr"))
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`cfg` and `cfg_attr` are not supported on arguments with a setter",
+            ));
+        }
+
+        let mut ty = (*input.ty).clone();
+        let mut impl_trait = FindImplTrait(None);
+        impl_trait.visit_type_mut(&mut ty);
+        if let Some(impl_trait) = impl_trait.0 {
+            return Err(syn::Error::new(
+                impl_trait,
+                "`impl Trait` arguments cannot have a setter, use a generic parameter",
+            ));
+        }
+        Lifetimes::Create(&mut self.lifetimes).visit_type_mut(&mut ty);
+        if let Some(self_ty) = self_ty {
+            ReplaceSelf(self_ty).visit_type_mut(&mut ty);
+        }
+
+        let (attrs, other_attrs) = input
+            .attrs
+            .drain(..)
+            .partition(|attr| attr.path().is_ident("builder") || attr.path().is_ident("doc"));
+        input.attrs = other_attrs;
+
+        self.names.push(ident.clone());
+        Ok(syn::Field {
+            attrs,
+            vis: syn::Visibility::Inherited,
+            mutability: syn::FieldMutability::None,
+            ident: Some(ident),
+            colon_token: Some(input.colon_token),
+            ty,
+        })
+    }
+}
+
+/// The name the builders of a function are prefixed with, such as `Connect` for `connect`, or
+/// `ClientConnect` for a method of `Client`. The builders of a constructor are named after the type
+/// alone.
+fn target_name(
+    sig: &syn::Signature,
+    self_ty: Option<&syn::Type>,
+    constructor: bool,
+) -> Result<String> {
+    let Some(self_ty) = self_ty else {
+        return Ok(convert_snake_case_to_upper_camel_case(&sig.ident));
+    };
+    let self_ident = type_ident(self_ty).ok_or_else(|| {
+        syn::Error::new_spanned(self_ty, "methods can only have a builder on named types")
+    })?;
+    if constructor {
+        Ok(self_ident.unraw().to_string())
+    } else {
+        Ok(format!(
+            "{}{}",
+            self_ident.unraw(),
+            convert_snake_case_to_upper_camel_case(&sig.ident)
+        ))
+    }
+}
+
+/// The generics of the entry point of a function, which are its own generics along with the
+/// `lifetimes` naming its elided lifetimes.
+fn entry_generics(generics: &syn::Generics, lifetimes: Vec<syn::Lifetime>) -> syn::Generics {
+    let (lifetime_params, other_params): (Vec<_>, Vec<_>) = generics
+        .params
+        .iter()
+        .cloned()
+        .partition(|param| matches!(param, syn::GenericParam::Lifetime(_)));
+    let params = lifetime_params
+        .into_iter()
+        .chain(
+            lifetimes
+                .into_iter()
+                .map(|lifetime| syn::GenericParam::Lifetime(syn::LifetimeParam::new(lifetime))),
+        )
+        .chain(other_params)
+        .collect();
+    syn::Generics {
+        params,
+        ..generics.clone()
+    }
+}
+
+/// The name of a type given by a path, such as `Client` for `net::Client<T>`.
+fn type_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            path.segments.last().map(|segment| &segment.ident)
+        }
+        _ => None,
+    }
+}
+
+/// Merges the generics of an `impl` block with those of one of its methods, keeping lifetimes
+/// ahead of every other parameter.
+fn merge_generics(outer: &syn::Generics, inner: &syn::Generics) -> syn::Generics {
+    let params = outer.params.iter().chain(&inner.params);
+    let (lifetimes, others): (Vec<_>, Vec<_>) =
+        params.partition(|param| matches!(param, syn::GenericParam::Lifetime(_)));
+    let predicates = outer
+        .where_clause
+        .iter()
+        .chain(&inner.where_clause)
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .collect::<Vec<_>>();
+
+    syn::Generics {
+        lt_token: outer.lt_token.or(inner.lt_token),
+        params: lifetimes.into_iter().chain(others).cloned().collect(),
+        gt_token: outer.gt_token.or(inner.gt_token),
+        where_clause: (!predicates.is_empty()).then(|| {
+            syn::parse_quote! {
+                where #(#predicates,)*
+            }
+        }),
+    }
+}
+
+/// Names or collects the lifetimes of a type, skipping function pointers and `Fn` traits, which
+/// elide lifetimes of their own.
+enum Lifetimes<'a> {
+    /// Names each elided lifetime with a new lifetime, which is added to the list.
+    Create(&'a mut Vec<syn::Lifetime>),
+    /// Names each elided lifetime with the given lifetime.
+    Assign(&'a syn::Lifetime),
+    /// Collects each distinct lifetime other than `'static`.
+    Collect(&'a mut Vec<syn::Lifetime>),
+}
+
+impl<'a> Lifetimes<'a> {
+    /// The lifetime replacing an elided lifetime, or `None` if lifetimes are only collected.
+    fn name(&mut self, span: proc_macro2::Span) -> Option<syn::Lifetime> {
+        match self {
+            Self::Create(lifetimes) => {
+                let lifetime =
+                    syn::Lifetime::new(&format!("'__better_builder_{}", lifetimes.len()), span);
+                lifetimes.push(lifetime.clone());
+                Some(lifetime)
+            }
+            Self::Assign(lifetime) => Some((*lifetime).clone()),
+            Self::Collect(_) => None,
+        }
+    }
+}
+
+impl<'a> VisitMut for Lifetimes<'a> {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = self.name(reference.and_token.span);
+        }
+        syn::visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if let Self::Collect(lifetimes) = self {
+            if lifetime.ident != "static" && !lifetimes.contains(lifetime) {
+                lifetimes.push(lifetime.clone());
+            }
+        } else if lifetime.ident == "_" {
+            if let Some(name) = self.name(lifetime.span()) {
+                *lifetime = name;
+            }
+        }
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// Replaces `Self` with the type implementing a method, as builders are defined outside of its
+/// `impl` block.
+///
+/// Paths such as `Self::check` in expressions become `<Cart>::check`.
+pub struct ReplaceSelf<'a>(pub &'a syn::Type);
+
+impl<'a> VisitMut for ReplaceSelf<'a> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+            if path.is_ident("Self") {
+                *ty = self.0.clone();
+                return;
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_expr_path_mut(&mut self, expr: &mut syn::ExprPath) {
+        let path = &mut expr.path;
+        if expr.qself.is_none()
+            && path.leading_colon.is_none()
+            && path.segments.len() > 1
+            && path.segments[0].ident == "Self"
+        {
+            let span = path.segments[0].ident.span();
+            expr.qself = Some(syn::QSelf {
+                lt_token: syn::Token![<](span),
+                ty: Box::new(self.0.clone()),
+                position: 0,
+                as_token: None,
+                gt_token: syn::Token![>](span),
+            });
+            path.leading_colon = Some(syn::Token![::](span));
+            path.segments = path.segments.iter().skip(1).cloned().collect();
+        }
+        syn::visit_mut::visit_expr_path_mut(self, expr);
+    }
+}
+
+/// Finds the first `impl Trait` in a type.
+struct FindImplTrait(Option<proc_macro2::Span>);
+
+impl VisitMut for FindImplTrait {
+    fn visit_type_impl_trait_mut(&mut self, impl_trait: &mut syn::TypeImplTrait) {
+        self.0.get_or_insert(impl_trait.span());
+    }
+}
@@ -0,0 +1,1904 @@
+//! The code generation behind [`better-builder`](https://docs.rs/better-builder), for use in other
+//! procedural macros or in build scripts.
+//!
+//! [`derive()`] expands a struct or enum as `#[derive(BetterBuilder)]` does, while [`builder`] and
+//! [`constructor`] expand the attributes of the same name.
+//!
+//! ```
+//! let input: syn::DeriveInput = syn::parse_quote! {
+//!     struct Config {
+//!         name: String,
+//!         timeout: Option<u32>,
+//!     }
+//! };
+//! let output = better_builder_core::derive(&input).unwrap();
+//! assert!(output.to_string().contains("struct ConfigBuilder"));
+//! ```
+#![warn(clippy::pedantic, clippy::nursery, clippy::all)]
+#![allow(elided_lifetimes_in_paths)]
+
+mod attributes;
+mod collection;
+mod docs;
+mod function;
+mod nested;
+mod skip;
+mod traits;
+mod unordered;
+mod validate;
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use attributes::{FieldDefault, FieldOptions, StructOptions};
+use function::{Callee, ReplaceSelf};
+use quote::quote;
+use syn::{ext::IdentExt, spanned::Spanned, visit_mut::VisitMut};
+
+type Result<T> = std::result::Result<T, syn::Error>;
+
+fn convert_snake_case_to_upper_camel_case(ident: &syn::Ident) -> String {
+    let ident_str = ident.unraw().to_string();
+    let mut camel_case = String::new();
+    let mut capitalize_next = true;
+    for c in ident_str.chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            camel_case.push(c.to_ascii_uppercase());
+            capitalize_next = false;
+        } else {
+            camel_case.push(c);
+        }
+    }
+
+    camel_case
+}
+
+fn convert_upper_camel_case_to_snake_case(ident: &syn::Ident) -> syn::Ident {
+    let ident_str = ident.unraw().to_string();
+    let chars = ident_str.chars().collect::<Vec<_>>();
+    let mut snake_case = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && index > 0 {
+            // Start a new word after a lowercase letter, or at the last capital of an acronym.
+            let previous = chars[index - 1];
+            let next_is_lowercase = chars.get(index + 1).map_or(false, char::is_ascii_lowercase);
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lowercase)
+            {
+                snake_case.push('_');
+            }
+        }
+        snake_case.push(c.to_ascii_lowercase());
+    }
+
+    syn::Ident::new(&snake_case, ident.span())
+}
+
+/// A setter method generated for a field, taking a single argument named after the field.
+struct Setter {
+    name: syn::Ident,
+    /// The type of the argument, or for a nested setter, the type built by the closure taken as
+    /// argument.
+    argument_type: proc_macro2::TokenStream,
+    /// Expression converting the argument into a value of the field's type.
+    value: proc_macro2::TokenStream,
+    /// Whether the setter takes a closure configuring the builder of `argument_type`, see
+    /// `#[builder(nested)]`.
+    nested: bool,
+}
+
+/// Data structure to store information about a field for later use in codegen.
+struct FieldData<'a> {
+    /// The name of the field on the builders, and of its setter.
+    ident: syn::Ident,
+    /// The field on the parent struct or variant, which is only distinct from `ident` for
+    /// unnamed fields.
+    member: syn::Member,
+    ty: &'a syn::Type,
+    /// The attributes of the field, from which doc comments are forwarded to its setters.
+    attrs: &'a [syn::Attribute],
+    options: FieldOptions,
+    builder_name_cache: RefCell<Option<syn::Ident>>,
+}
+
+impl<'a> FieldData<'a> {
+    fn new(ident: &syn::Ident, ty: &'a syn::Type) -> Self {
+        Self {
+            ident: ident.clone(),
+            member: syn::Member::Named(ident.clone()),
+            ty,
+            attrs: &[],
+            options: FieldOptions::default(),
+            builder_name_cache: RefCell::new(None),
+        }
+    }
+
+    /// Creates the field data for the field at `index` of a struct or variant, parsing any
+    /// `#[builder(...)]` attributes.
+    ///
+    /// Unnamed fields are named after their position, such as `_0`, unless a name is provided with
+    /// `#[builder(name = "...")]`.
+    fn from_field(index: usize, field: &'a syn::Field) -> Result<Self> {
+        let options = FieldOptions::from_attributes(&field.attrs)?;
+        let mut field_data = match (&field.ident, &options.name) {
+            (Some(ident), None) => Self::new(ident, &field.ty),
+            (None, name) => {
+                let ident = name
+                    .clone()
+                    .unwrap_or_else(|| quote::format_ident!("_{index}", span = field.ty.span()));
+                Self {
+                    member: syn::Member::Unnamed(syn::Index {
+                        index: u32::try_from(index).expect("Too many fields"),
+                        span: field.ty.span(),
+                    }),
+                    ..Self::new(&ident, &field.ty)
+                }
+            }
+            (Some(_), Some(name)) => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "`name` can only be used on unnamed fields",
+                ));
+            }
+        };
+        field_data.attrs = &field.attrs;
+        field_data.options = options;
+
+        if field_data.options.skip.is_some() {
+            if field_data.has_setter_options() {
+                return Err(syn::Error::new_spanned(
+                    &field_data.ident,
+                    "`skip` cannot be combined with options configuring the setter",
+                ));
+            }
+            return Ok(field_data);
+        }
+        if field_data.is_phantom_data() && !field_data.has_setter_options() {
+            field_data.options.skip = Some(FieldDefault::Trait);
+            return Ok(field_data);
+        }
+
+        if field_data.options.required
+            && (field_data.options.optional || field_data.options.default.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                &field_data.ident,
+                "`required` cannot be combined with `optional` or `default`",
+            ));
+        }
+        if let Some(each) = &field_data.options.each {
+            if field_data.options.required || field_data.options.optional {
+                return Err(syn::Error::new_spanned(
+                    each,
+                    "`each` cannot be combined with `required` or `optional`",
+                ));
+            }
+            if field_data.collection_item_type().is_none() {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "`each` can only be used on collections such as `Vec<T>` or `HashMap<K, V>`",
+                ));
+            }
+            // The collection starts out empty, so it is never required.
+            field_data
+                .options
+                .default
+                .get_or_insert(FieldDefault::Trait);
+        }
+        if field_data.options.strip_option && field_data.option_inner_type().is_none() {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`strip_option` can only be used on fields of type `Option<T>`",
+            ));
+        }
+        if field_data.options.optional && !field_data.may_be_option() {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`optional` requires an `Option<T>` field",
+            ));
+        }
+        if field_data.options.state_alias.is_some() && !field_data.is_required() {
+            return Err(syn::Error::new_spanned(
+                &field_data.options.state_alias,
+                "`state_alias` can only be used on required fields",
+            ));
+        }
+        if field_data.options.nested
+            && (field_data.options.into || field_data.options.each.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                &field_data.ident,
+                "`nested` cannot be combined with `into` or `each`",
+            ));
+        }
+
+        Ok(field_data)
+    }
+
+    /// Checks if the field is optional.
+    ///
+    /// Returns `true` if the field's type is [`std::option::Option`], unless this has been
+    /// overridden with `#[builder(required)]` or `#[builder(optional)]`.
+    fn is_optional(&self) -> bool {
+        if self.options.optional {
+            true
+        } else if self.options.required {
+            false
+        } else {
+            self.option_inner_type().is_some()
+        }
+    }
+
+    /// Checks if the field must be provided through the chain of required builders.
+    ///
+    /// Returns `false` if the field is optional or has a default value, in which case it is set
+    /// on the final builder instead.
+    fn is_required(&self) -> bool {
+        !self.is_optional() && self.options.default.is_none()
+    }
+
+    /// The type used to store the field on the final builder.
+    ///
+    /// Fields with a default value are wrapped in an [`Option`] so that unset fields can be
+    /// filled in when the builder is built.
+    fn storage_type(&self) -> proc_macro2::TokenStream {
+        let field_type = self.ty;
+        if self.options.default.is_some() {
+            quote! { ::core::option::Option<#field_type> }
+        } else {
+            quote! { #field_type }
+        }
+    }
+
+    /// Wraps the value passed to a setter so it can be stored on the final builder.
+    fn storage_value(&self, value: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if self.options.default.is_some() {
+            quote! { ::core::option::Option::Some(#value) }
+        } else {
+            quote! { #value }
+        }
+    }
+
+    /// Extracts `T` from a field of type `Option<T>`.
+    ///
+    /// The type may be written as `Option<T>`, `option::Option<T>`, or fully qualified through
+    /// `std` or `core`. Type aliases of [`Option`] are not recognised.
+    fn option_inner_type(&self) -> Option<&'a syn::Type> {
+        const OPTION_PATHS: &[&[&str]] = &[
+            &["Option"],
+            &["option", "Option"],
+            &["std", "option", "Option"],
+            &["core", "option", "Option"],
+        ];
+
+        let syn::Type::Path(syn::TypePath { qself: None, path }) = self.ty else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        let is_option = OPTION_PATHS.iter().any(|option_path| {
+            option_path.len() == path.segments.len()
+                && path
+                    .segments
+                    .iter()
+                    .zip(option_path.iter())
+                    .all(|(segment, name)| segment.ident == name)
+        });
+        let parents_have_arguments = path
+            .segments
+            .iter()
+            .rev()
+            .skip(1)
+            .any(|segment| !segment.arguments.is_none());
+        if !is_option || parents_have_arguments {
+            return None;
+        }
+
+        let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+        match arguments.args.iter().collect::<Vec<_>>().as_slice() {
+            [syn::GenericArgument::Type(inner)] => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Checks if the field's type may be an [`Option`], which is the case for `Option<T>` itself
+    /// and any path which could be a type alias of it.
+    ///
+    /// References, tuples, arrays and other non-path types are never an [`Option`], and neither
+    /// are primitive types or the types of the prelude, such as `String` or `Vec<T>`.
+    fn may_be_option(&self) -> bool {
+        const NOT_OPTION: &[&str] = &[
+            "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+            "i64", "i128", "isize", "f32", "f64", "String", "Vec", "Box", "Result",
+        ];
+
+        match self.ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => {
+                self.option_inner_type().is_some()
+                    || path.leading_colon.is_some()
+                    || path.segments.len() > 1
+                    || !NOT_OPTION.iter().any(|name| path.segments[0].ident == name)
+            }
+            syn::Type::Array(_)
+            | syn::Type::BareFn(_)
+            | syn::Type::ImplTrait(_)
+            | syn::Type::Never(_)
+            | syn::Type::Ptr(_)
+            | syn::Type::Reference(_)
+            | syn::Type::Slice(_)
+            | syn::Type::TraitObject(_)
+            | syn::Type::Tuple(_) => false,
+            // Qualified paths, macros and groups may still expand to an `Option<T>`.
+            _ => true,
+        }
+    }
+
+    /// The type accepted by the primary setter of this field, which is `T` rather than
+    /// `Option<T>` if the field has `strip_option` set.
+    fn setter_type(&self) -> &'a syn::Type {
+        if self.options.strip_option {
+            self.option_inner_type().unwrap_or(self.ty)
+        } else {
+            self.ty
+        }
+    }
+
+    /// The name of the setter of this field, which is the name of the field unless it is renamed
+    /// with `#[builder(setter(name = "..."))]`.
+    fn setter_name(&self) -> syn::Ident {
+        self.options
+            .setter_name
+            .clone()
+            .unwrap_or_else(|| self.ident.clone())
+    }
+
+    /// Generates the setters of this field.
+    ///
+    /// Every field has a setter with the same name as the field. If `strip_option` is set this
+    /// setter takes `T` rather than `Option<T>`, and an additional `maybe_` setter taking the
+    /// `Option<T>` itself is generated. If `nested` is set the setter takes a closure configuring
+    /// the builder of `T` instead.
+    fn setters(&self) -> Vec<Setter> {
+        let field_name = &self.ident;
+        let setter_name = self.setter_name();
+        let setter_type = self.setter_type();
+        // The value of a nested field is built by `Setter::generate`, under the name of the field.
+        let (argument_type, mut value) = if self.options.into {
+            (
+                quote! { impl ::core::convert::Into<#setter_type> },
+                quote! { ::core::convert::Into::into(#field_name) },
+            )
+        } else {
+            (quote! { #setter_type }, quote! { #field_name })
+        };
+
+        if !self.options.strip_option {
+            return vec![Setter {
+                name: setter_name,
+                argument_type,
+                value,
+                nested: self.options.nested,
+            }];
+        }
+
+        value = quote! { ::core::option::Option::Some(#value) };
+        let field_type = self.ty;
+        let maybe_name =
+            quote::format_ident!("maybe_{}", setter_name.unraw(), span = setter_name.span());
+        vec![
+            Setter {
+                name: setter_name,
+                argument_type,
+                value,
+                nested: self.options.nested,
+            },
+            Setter {
+                name: maybe_name,
+                argument_type: quote! { #field_type },
+                value: quote! { #field_name },
+                nested: false,
+            },
+        ]
+    }
+
+    /// Checks if a struct level `#[builder(into)]` should apply to the setter of this field.
+    ///
+    /// Setters accepting `impl Into<T>` cannot infer the type of numeric literals, as many numeric
+    /// types convert into each other, nor can they infer a generic parameter of the parent struct.
+    /// Fields of these types keep their plain setters unless `into` is set on the field itself.
+    fn infers_with_into(&self, generics: &syn::Generics) -> bool {
+        const NUMERIC_TYPES: &[&str] = &[
+            "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+            "f32", "f64",
+        ];
+
+        fn mentions_any(tokens: proc_macro2::TokenStream, idents: &[&syn::Ident]) -> bool {
+            tokens.into_iter().any(|token| match token {
+                proc_macro2::TokenTree::Ident(ident) => idents.contains(&&ident),
+                proc_macro2::TokenTree::Group(group) => mentions_any(group.stream(), idents),
+                _ => false,
+            })
+        }
+
+        let is_numeric = match self.setter_type() {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => path
+                .get_ident()
+                .map_or(false, |ident| NUMERIC_TYPES.iter().any(|ty| ident == ty)),
+            _ => false,
+        };
+        let type_params = generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect::<Vec<_>>();
+
+        let setter_type = self.setter_type();
+        !is_numeric && !mentions_any(quote! { #setter_type }, &type_params)
+    }
+
+    /// Generates the expression used by `build()` to take the field out of the final builder,
+    /// filling in the default value if the field was never set.
+    fn build_value(&self) -> proc_macro2::TokenStream {
+        let field_name = &self.ident;
+        match &self.options.default {
+            Some(FieldDefault::Trait) => quote! {
+                self.#field_name.unwrap_or_else(::core::default::Default::default)
+            },
+            Some(FieldDefault::Expr(expr)) => quote! {
+                self.#field_name.unwrap_or_else(|| #expr)
+            },
+            None => quote! { self.#field_name },
+        }
+    }
+
+    /// Generates a builder name for the field.
+    ///
+    /// The builder name is generated based on the state prefix and the field name.
+    /// If there are other builders with the same name, a suffix "Missing" followed by a number is
+    /// added to the builder name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `state_prefix` - The prefix of every state, such as `MyStructBuilderMissing`.
+    /// * `other_builders` - A mutable reference to a `HashMap` that stores other builders.
+    ///
+    /// A state named with `#[builder(state_alias = "...")]` keeps that name instead.
+    ///
+    /// ## Returns
+    ///
+    /// The generated builder name as a `syn::Ident`.
+    fn generate_builder_name(
+        &self,
+        state_prefix: &str,
+        other_builders: &mut HashMap<String, u16>,
+    ) -> syn::Ident {
+        if let Some(builder_name) = &self.builder_name_cache.borrow().as_ref() {
+            return (**builder_name).clone();
+        }
+        if let Some(alias) = &self.options.state_alias {
+            return alias.clone();
+        }
+
+        let orig_field_name = &self.ident;
+        let field_name = convert_snake_case_to_upper_camel_case(orig_field_name);
+
+        let mut builder_name = format!("{state_prefix}{field_name}");
+        let count = other_builders.entry(builder_name.clone()).or_insert(0);
+        if *count > 0 {
+            builder_name.push_str(&count.to_string());
+        }
+        *count = (*count)
+            .checked_add(1)
+            .expect("Overflow in builder name generation");
+
+        let new_builder_name = syn::Ident::new(&builder_name, orig_field_name.span());
+
+        self.builder_name_cache
+            .replace(Some(new_builder_name.clone()));
+
+        new_builder_name
+    }
+
+    fn get_name_and_type(&self) -> proc_macro2::TokenStream {
+        let field_name = &self.ident;
+        let field_type = self.storage_type();
+        quote! {
+            #field_name: #field_type,
+        }
+    }
+}
+
+struct BetterBuilderGenerator<'a> {
+    original_data: &'a syn::DeriveInput,
+    /// The enum variant constructed by this generator, if deriving on an enum.
+    variant: Option<&'a syn::Variant>,
+    options: StructOptions,
+    fields: Vec<FieldData<'a>>,
+    /// Fields with `#[builder(skip)]`, which are only initialised when the struct is built.
+    skipped: Vec<FieldData<'a>>,
+    /// The function called by the builder, if generated by `#[better_builder::builder]`.
+    function: Option<Callee>,
+}
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// Creates a generator for each builder needed by `original_data`: a single builder for a
+    /// struct, or a builder for each variant of an enum.
+    pub fn from_derive_input(original_data: &'a syn::DeriveInput) -> Result<Vec<Self>> {
+        let struct_name = &original_data.ident;
+        let (_, ty_generics, _) = original_data.generics.split_for_impl();
+        let self_ty: syn::Type = syn::parse_quote! { #struct_name #ty_generics };
+
+        match &original_data.data {
+            syn::Data::Struct(data) => {
+                let options = StructOptions::from_attributes(&original_data.attrs)?;
+                let mut generator = Self::new(original_data, None, options, &data.fields)?;
+                generator.replace_self(&self_ty);
+                Ok(vec![generator])
+            }
+            syn::Data::Enum(data) => {
+                // Each variant has its own builders, so their names cannot be shared by the whole
+                // enum.
+                let options = StructOptions::from_attributes(&original_data.attrs)?;
+                let shared_name = options
+                    .name
+                    .map(|name| name.span())
+                    .or_else(|| options.constructor.map(|name| name.span()))
+                    .or_else(|| options.state_prefix.map(|prefix| prefix.span()));
+                if let Some(span) = shared_name {
+                    return Err(syn::Error::new(
+                        span,
+                        "`name`, `constructor` and `state_prefix` must be set on each variant",
+                    ));
+                }
+
+                data.variants
+                    .iter()
+                    .map(|variant| {
+                        let attrs = original_data
+                            .attrs
+                            .iter()
+                            .chain(&variant.attrs)
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        let options = StructOptions::from_attributes(&attrs)?;
+                        let mut generator =
+                            Self::new(original_data, Some(variant), options, &variant.fields)?;
+                        generator.replace_self(&self_ty);
+                        Ok(generator)
+                    })
+                    .collect()
+            }
+            syn::Data::Union(_) => {
+                // SAFETY: This is a compile time error, which is not included in coverage. We have a test
+                // for this specific case in: tests/compile_tests/should_fail/error_on_union.rs
+                Err(syn::Error::new_spanned(
+                    original_data,
+                    "BetterBuilder can only be derived on structs and enums.",
+                ))
+            }
+        }
+    }
+
+    fn new(
+        original_data: &'a syn::DeriveInput,
+        variant: Option<&'a syn::Variant>,
+        options: StructOptions,
+        fields: &'a syn::Fields,
+    ) -> Result<Self> {
+        let (skipped, mut fields): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| FieldData::from_field(index, field))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .partition(|field| field.options.skip.is_some());
+        fields.sort_by_key(|field| !field.is_required());
+
+        for field in &mut fields {
+            field.options.strip_option |=
+                options.strip_option && field.option_inner_type().is_some();
+            field.options.into |= options.into
+                && !field.options.nested
+                && field.infers_with_into(&original_data.generics);
+            if let (Some(prefix), None) = (&options.setter_prefix, &field.options.setter_name) {
+                field.options.setter_name = Some(quote::format_ident!(
+                    "{}{}",
+                    prefix.value(),
+                    field.ident.unraw(),
+                    span = field.ident.span()
+                ));
+            }
+        }
+
+        // Setters may share a builder, so their names must be unique across the whole struct.
+        let mut setter_names = HashSet::new();
+        let all_setter_names = fields.iter().flat_map(|field| {
+            let each_names = field.each_names().map(|(each, extend)| [each, extend]);
+            field
+                .setters()
+                .into_iter()
+                .map(|setter| setter.name)
+                .chain(each_names.into_iter().flatten())
+        });
+        for name in all_setter_names {
+            if !setter_names.insert(name.unraw()) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("duplicate setter `{}`", name.unraw()),
+                ));
+            }
+        }
+
+        let generator = Self {
+            original_data,
+            variant,
+            options,
+            fields,
+            skipped,
+            function: None,
+        };
+        let build_fn_name = generator.build_fn_name().unraw();
+        if let Some(setter) = setter_names.get(&build_fn_name) {
+            return Err(syn::Error::new(
+                setter.span(),
+                format!("setter `{build_fn_name}` collides with the build method"),
+            ));
+        }
+        Ok(generator)
+    }
+
+    /// Replaces `Self` with `self_ty` in the expressions given to the options, such as
+    /// `default = Self::DEFAULT_PORT` or `skip = Self::new_cache()`, as they end up in the `impl` blocks of the builders, where
+    /// `Self` is the builder.
+    pub fn replace_self(&mut self, self_ty: &syn::Type) {
+        let mut replace_self = ReplaceSelf(self_ty);
+        if let Some(validate) = &mut self.options.validate {
+            replace_self.visit_expr_mut(validate);
+        }
+        for field in self.fields.iter_mut().chain(&mut self.skipped) {
+            let options = &mut field.options;
+            let defaults = [&mut options.default, &mut options.skip];
+            for default in defaults.into_iter().flatten() {
+                if let FieldDefault::Expr(expr) = default {
+                    replace_self.visit_expr_mut(expr);
+                }
+            }
+            if let Some(validate) = &mut options.validate {
+                replace_self.visit_expr_mut(validate);
+            }
+        }
+    }
+
+    pub fn fields(&self) -> &[FieldData] {
+        &self.fields
+    }
+
+    pub const fn visibility(&self) -> &syn::Visibility {
+        &self.original_data.vis
+    }
+
+    pub const fn struct_name(&self) -> &syn::Ident {
+        &self.original_data.ident
+    }
+
+    /// The name every generated builder is prefixed with, which is the name of the struct, or the
+    /// name of the enum followed by the name of the variant.
+    pub fn target_name(&self) -> syn::Ident {
+        let struct_name = self.struct_name();
+        self.variant.map_or_else(
+            || struct_name.clone(),
+            |variant| quote::format_ident!("{}{}", struct_name, variant.ident.unraw()),
+        )
+    }
+
+    /// The path used to construct the target of the builder, such as `Cart` or `Message::Ping`.
+    pub fn target_path(&self) -> proc_macro2::TokenStream {
+        let struct_name = self.struct_name();
+        self.variant.map_or_else(
+            || quote! { #struct_name },
+            |variant| {
+                let variant_name = &variant.ident;
+                quote! { #struct_name::#variant_name }
+            },
+        )
+    }
+
+    /// The name of the associated function which creates a new builder, which is `builder` for a
+    /// struct, or the name of the variant in snake case followed by `_builder` for an enum.
+    pub fn entry_point_name(&self) -> syn::Ident {
+        if let Some(constructor) = &self.options.constructor {
+            return constructor.clone();
+        }
+        if let Some(function) = &self.function {
+            return function.entry_point_name();
+        }
+        self.variant.map_or_else(
+            || syn::Ident::new("builder", proc_macro2::Span::call_site()),
+            |variant| {
+                let variant_name = convert_upper_camel_case_to_snake_case(&variant.ident);
+                quote::format_ident!("{}_builder", variant_name)
+            },
+        )
+    }
+
+    pub const fn generics(&self) -> &syn::Generics {
+        &self.original_data.generics
+    }
+
+    /// Generates the attributes added to every builder through `#[builder(derive(...))]` and
+    /// `#[builder(attr(...))]`.
+    pub fn generate_builder_attributes(&self) -> proc_macro2::TokenStream {
+        let derives = &self.options.derives;
+        let attrs = &self.options.attrs;
+        let derive = (!derives.is_empty()).then(|| quote! { #[derive(#(#derives),*)] });
+        quote! {
+            #derive
+            #(#[#attrs])*
+        }
+    }
+
+    /// Generates the marker field declaration and initialiser shared by every builder.
+    ///
+    /// A builder state may not yet hold any field which uses a given type or lifetime parameter of
+    /// the parent struct, so each builder carries a `PhantomData` over all of them. Const generics
+    /// are exempt from this check and are not included. If the parent struct has no type or
+    /// lifetime parameters, no marker is generated.
+    ///
+    /// ## Returns
+    ///
+    /// A tuple of the field declaration and the field initialiser, both of which are empty if no
+    /// marker is required.
+    pub fn generate_marker_field(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let generics = self.generics();
+        if generics.lifetimes().next().is_none() && generics.type_params().next().is_none() {
+            return (quote! {}, quote! {});
+        }
+
+        let lifetimes = generics.lifetimes().map(|param| &param.lifetime);
+        let type_params = generics.type_params().map(|param| &param.ident);
+        (
+            quote! {
+                __better_builder_marker: ::core::marker::PhantomData<
+                    fn() -> (#(&#lifetimes (),)* #(#type_params,)*)
+                >,
+            },
+            quote! {
+                __better_builder_marker: ::core::marker::PhantomData,
+            },
+        )
+    }
+
+    pub fn final_builder_name(&self) -> syn::Ident {
+        if let Some(name) = &self.options.name {
+            return name.clone();
+        }
+        let target_name = self.target_name();
+        let builder_name = format!("{target_name}Builder");
+        syn::Ident::new(&builder_name, target_name.span())
+    }
+
+    /// The name of the method on the final builder which builds the struct, which is `try_build`
+    /// by default if the struct is validated, or `call` for a function other than a constructor.
+    pub fn build_fn_name(&self) -> syn::Ident {
+        self.options.build_fn.clone().unwrap_or_else(|| {
+            let name = self.function.as_ref().map_or_else(
+                || {
+                    if self.is_validated() {
+                        "try_build"
+                    } else {
+                        "build"
+                    }
+                },
+                Callee::build_fn_name,
+            );
+            syn::Ident::new(name, proc_macro2::Span::call_site())
+        })
+    }
+
+    /// Generates the method building the struct, where `values` holds the expression taking each
+    /// field out of the builder.
+    pub fn generate_build_fn(
+        &self,
+        values: &[proc_macro2::TokenStream],
+    ) -> proc_macro2::TokenStream {
+        let struct_name = self.struct_name();
+        let target_path = self.target_path();
+        if let Some(function) = &self.function {
+            return self.generate_call_fn(function, values);
+        }
+
+        let build_fn_name = self.build_fn_name();
+        let docs = self.build_fn_docs();
+        let (_, ty_generics, _) = self.generics().split_for_impl();
+
+        if self.is_validated() {
+            let error_name = self.error_name();
+            let body = self.generate_validated_build(values);
+            return quote! {
+                #docs
+                pub fn #build_fn_name(
+                    self,
+                ) -> ::core::result::Result<#struct_name #ty_generics, #error_name> {
+                    #body
+                }
+            };
+        }
+
+        let members = self.fields.iter().map(|field| &field.member);
+        let skipped_values = self.generate_skipped_values();
+        quote! {
+            #docs
+            pub fn #build_fn_name(self) -> #struct_name #ty_generics {
+                #target_path {
+                    #(#members: #values,)*
+                    #skipped_values
+                }
+            }
+        }
+    }
+
+    /// The prefix of the name of each intermediate state, which is followed by the name of the
+    /// field the state is missing.
+    ///
+    /// The states are declared in an anonymous constant next to the struct, so they cannot be named
+    /// elsewhere. Their names start with `__` so they do not shadow the types of the fields.
+    pub fn state_prefix(&self) -> String {
+        self.options.state_prefix.as_ref().map_or_else(
+            || format!("__{}BuilderMissing", self.target_name()),
+            |prefix| format!("__{}", prefix.value()),
+        )
+    }
+
+    /// The names of all fields which are set on the final builder, and so start out as `None`.
+    pub fn optional_names(&self) -> Vec<&syn::Ident> {
+        self.fields
+            .iter()
+            .filter(|field| !field.is_required())
+            .map(|field| &field.ident)
+            .collect()
+    }
+
+    pub fn generate_optional_setters(&self) -> Vec<proc_macro2::TokenStream> {
+        let crate_path = &self.crate_path();
+        let optional_fields = self.fields.iter().filter(|field| !field.is_required());
+        optional_fields
+            .flat_map(|field| {
+                let field_name = &field.ident;
+                field
+                    .setters()
+                    .into_iter()
+                    .map(move |setter| {
+                        let value = field.storage_value(&setter.value);
+                        setter.generate(
+                            field,
+                            &quote! { mut self },
+                            &quote! { Self },
+                            &quote! {
+                                self.#field_name = #value;
+                                self
+                            },
+                            crate_path,
+                        )
+                    })
+                    .chain(field.each_setters())
+            })
+            .collect()
+    }
+
+    /// Generates the final builder, on which every required field is set.
+    ///
+    /// ## Returns
+    ///
+    /// A tuple of the items which can be named, which are the final builder itself and the entry
+    /// point of a free function, and of the items declared in the anonymous constant with the
+    /// intermediate states.
+    pub fn generate_final_builder(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let builder_name = self.final_builder_name();
+        let visibility = self.visibility();
+        let generics = self.generics();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let (marker_decl, _) = self.generate_marker_field();
+        let (receiver_decl, _) = self.generate_receiver_field();
+        let struct_fields = self.fields.iter().map(FieldData::get_name_and_type);
+
+        let setters = self.generate_optional_setters();
+
+        let values = self
+            .fields
+            .iter()
+            .map(FieldData::build_value)
+            .collect::<Vec<_>>();
+        let build_fn = self.generate_build_fn(&values);
+
+        // If the first field is required the struct is built starting from its builder, which
+        // holds no required fields yet. Otherwise, the final builder is used directly. Either way,
+        // every optional field starts out unset.
+        let first_builder_name = match self.fields.first() {
+            Some(field) if field.is_required() => {
+                let state_name =
+                    field.generate_builder_name(&self.state_prefix(), &mut HashMap::new());
+                quote! { #state_name }
+            }
+            _ => quote! { #builder_name },
+        };
+        let optional_fields = self.optional_names();
+        let complete = quote! { #builder_name #ty_generics };
+        let (exposed_entry_point, entry_point) = self.generate_entry_point(
+            &first_builder_name,
+            &quote! { #ty_generics },
+            &quote! { #(#optional_fields: None,)* },
+            &complete,
+        );
+        let start = quote! { #first_builder_name #ty_generics };
+        let trait_impls = self.generate_trait_impls(&start, &complete);
+        let to_builder = self.generate_to_builder(&complete);
+
+        let builder_attributes = self.generate_builder_attributes();
+        let docs = self.final_builder_docs();
+
+        let exposed = quote! {
+            #docs
+            #builder_attributes
+            #visibility struct #builder_name #generics #where_clause {
+                #(#struct_fields)*
+                #receiver_decl
+                #marker_decl
+            }
+
+            #exposed_entry_point
+        };
+        let hidden = quote! {
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                #(#setters)*
+
+                #build_fn
+            }
+
+            #entry_point
+            #to_builder
+            #trait_impls
+        };
+        (exposed, hidden)
+    }
+
+    /// Generates the function creating the first builder, named `name` with generic `arguments`,
+    /// whose fields other than the marker are initialised with `fields_init`. The `complete`
+    /// builder names the first builder for a free function.
+    ///
+    /// ## Returns
+    ///
+    /// A tuple of the items which can be named, which is only the entry point of a free function,
+    /// and of the items declared in the anonymous constant with the intermediate states.
+    pub fn generate_entry_point(
+        &self,
+        name: &proc_macro2::TokenStream,
+        arguments: &proc_macro2::TokenStream,
+        fields_init: &proc_macro2::TokenStream,
+        complete: &proc_macro2::TokenStream,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        if let Some(function) = &self.function {
+            return self.generate_function_entry_point(
+                function,
+                name,
+                arguments,
+                fields_init,
+                complete,
+            );
+        }
+
+        let struct_name = self.struct_name();
+        let entry_point_name = self.entry_point_name();
+        let docs = self.entry_point_docs();
+        let (impl_generics, ty_generics, where_clause) = self.generics().split_for_impl();
+        let (_, marker_init) = self.generate_marker_field();
+
+        let entry_point = quote! {
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #docs
+                pub fn #entry_point_name() -> #name #arguments {
+                    #name {
+                        #fields_init
+                        #marker_init
+                    }
+                }
+            }
+        };
+        (quote! {}, entry_point)
+    }
+
+    /// Generates `to_builder()` and the matching [`From`] implementation, turning an existing
+    /// struct back into its complete builder of type `complete`.
+    ///
+    /// Nothing is generated for enums, as the value may hold a different variant, nor for
+    /// functions.
+    pub fn generate_to_builder(
+        &self,
+        complete: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        if self.variant.is_some() || self.function.is_some() {
+            return quote! {};
+        }
+
+        let struct_name = self.struct_name();
+        let builder_name = self.final_builder_name();
+        let docs = self.to_builder_docs();
+        let (impl_generics, ty_generics, where_clause) = self.generics().split_for_impl();
+        let (_, marker_init) = self.generate_marker_field();
+        let members = self.fields.iter().map(|field| &field.member);
+        let names = self
+            .fields
+            .iter()
+            .map(|field| &field.ident)
+            .collect::<Vec<_>>();
+        // Skipped fields are dropped, as they are initialised again when the struct is built.
+        let rest = (!self.skipped.is_empty()).then(|| quote! { .. });
+        let values = self.fields.iter().map(|field| {
+            let name = &field.ident;
+            if !field.is_required() {
+                field.storage_value(&quote! { #name })
+            } else if self.options.unordered {
+                quote! { (#name,) }
+            } else {
+                quote! { #name }
+            }
+        });
+
+        quote! {
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #docs
+                #[allow(non_shorthand_field_patterns)]
+                pub fn to_builder(self) -> #complete {
+                    let Self { #(#members: #names,)* #rest } = self;
+                    #builder_name {
+                        #(#names: #values,)*
+                        #marker_init
+                    }
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#struct_name #ty_generics> for #complete
+            #where_clause
+            {
+                fn from(value: #struct_name #ty_generics) -> Self {
+                    value.to_builder()
+                }
+            }
+        }
+    }
+
+    /// Generates the chain of builders for the required fields, in which each builder sets a
+    /// single field before moving on to the builder for the next one.
+    ///
+    /// ## Returns
+    ///
+    /// A tuple of the states named with `#[builder(state_alias = "...")]`, and of every other
+    /// item, which is declared in the anonymous constant so the states cannot be named.
+    pub fn generate_required_builders(
+        &self,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let state_prefix = self.state_prefix();
+        let crate_path = self.crate_path();
+        let mut other_builders = HashMap::new();
+        let mut fields_used_so_far: Vec<&FieldData> = Vec::new();
+        let mut exposed = quote! {};
+        let mut hidden = quote! {};
+        let optional_fields = self
+            .fields()
+            .iter()
+            .filter(|field| !field.is_required())
+            .collect::<Vec<_>>();
+        let optional_names = self.optional_names();
+        let optional_setters = self.generate_optional_setters();
+
+        for (index, field) in self.fields().iter().enumerate() {
+            if !field.is_required() {
+                break;
+            }
+
+            let field_name = &field.ident;
+            let builder_name = field.generate_builder_name(&state_prefix, &mut other_builders);
+
+            // Optional fields are carried through every state, so they may be set at any point.
+            let struct_def_fields = fields_used_so_far
+                .iter()
+                .copied()
+                .chain(optional_fields.iter().copied())
+                .map(FieldData::get_name_and_type);
+
+            let visibility = self.visibility();
+            let generics = self.generics();
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let (marker_decl, marker_init) = self.generate_marker_field();
+            let (receiver_decl, receiver_init) = self.generate_receiver_field();
+
+            // The next builder is either the builder for the next required field, or the final
+            // builder.
+            let next_builder_name = match self.fields().get(index + 1) {
+                Some(next_field) if next_field.is_required() => {
+                    next_field.generate_builder_name(&state_prefix, &mut other_builders)
+                }
+                _ => self.final_builder_name(),
+            };
+
+            let setters = field.setters().into_iter().map(|setter| {
+                let value = &setter.value;
+                let builder_fields = fields_used_so_far.iter().map(|a| &a.ident);
+                setter.generate(
+                    field,
+                    &quote! { self },
+                    &quote! { #next_builder_name #ty_generics },
+                    &quote! {
+                        #next_builder_name {
+                            #field_name: #value,
+                            #(#builder_fields: self.#builder_fields,)*
+                            #(#optional_names: self.#optional_names,)*
+                            #receiver_init
+                            #marker_init
+                        }
+                    },
+                    &crate_path,
+                )
+            });
+
+            let builder_attributes = self.generate_builder_attributes();
+            let docs = self.state_docs(index);
+            let state = quote! {
+                #docs
+                #builder_attributes
+                #visibility struct #builder_name #generics #where_clause {
+                    #(#struct_def_fields)*
+                    #receiver_decl
+                    #marker_decl
+                }
+            };
+            if field.options.state_alias.is_some() {
+                exposed.extend(state);
+            } else {
+                hidden.extend(state);
+            }
+            hidden.extend(quote! {
+                impl #impl_generics #builder_name #ty_generics #where_clause {
+                    #(#setters)*
+
+                    #(#optional_setters)*
+                }
+            });
+
+            fields_used_so_far.push(field);
+        }
+
+        (exposed, hidden)
+    }
+
+    /// Generates every builder for the struct or variant.
+    ///
+    /// The intermediate states are declared in an anonymous constant along with every item naming
+    /// them, so only the entry point, the final builder and the states named with
+    /// `#[builder(state_alias = "...")]` can be named.
+    pub fn generate(&self) -> proc_macro2::TokenStream {
+        let (exposed, hidden) = if self.options.unordered {
+            self.generate_unordered_builder()
+        } else {
+            let (required_exposed, required_hidden) = self.generate_required_builders();
+            let (final_exposed, final_hidden) = self.generate_final_builder();
+            (
+                quote! { #required_exposed #final_exposed },
+                quote! { #required_hidden #final_hidden },
+            )
+        };
+        let error_type = self.generate_error_type();
+        quote! {
+            #exposed
+            #error_type
+
+            const _: () = {
+                #hidden
+            };
+        }
+    }
+}
+
+/// Generates the builders for a struct or enum, as done by `#[derive(BetterBuilder)]`.
+///
+/// The options of the builders are read from the `#[builder(...)]` attributes of `input`.
+///
+/// # Errors
+///
+/// Returns an error if an option is invalid, or if `input` is a union.
+pub fn derive(input: &syn::DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let output = BetterBuilderGenerator::from_derive_input(input)?
+        .iter()
+        .map(BetterBuilderGenerator::generate)
+        .collect();
+
+    Ok(output)
+}
+
+/// Generates the builders for a struct or enum like [`derive()`], with additional struct options.
+///
+/// The `options` are written as inside `#[builder(...)]`, such as `unordered, into`, and cannot
+/// repeat an option already set by the attributes of `input`.
+///
+/// # Errors
+///
+/// Returns an error if an option is invalid, or if `input` is a union.
+pub fn derive_with_options(
+    input: &syn::DeriveInput,
+    options: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream> {
+    let mut input = input.clone();
+    input.attrs.push(syn::parse_quote! { #[builder(#options)] });
+    derive(&input)
+}
+
+/// Generates a builder for the arguments of a function, or of the marked methods of an `impl`
+/// block, as done by `#[better_builder::builder]`.
+///
+/// The `args` are the options passed to the attribute, and the returned tokens include `item`
+/// with the options of its arguments removed.
+///
+/// # Errors
+///
+/// Returns an error if an option is invalid, or if `item` is not a function or an inherent `impl`
+/// block.
+pub fn builder(
+    args: proc_macro2::TokenStream,
+    item: syn::Item,
+) -> Result<proc_macro2::TokenStream> {
+    function::expand(args, item)
+}
+
+/// Expands `#[better_builder::constructor]` used outside of an `impl` block with
+/// `#[better_builder::builder]`, which reports an error alongside the unchanged `item`.
+#[must_use]
+pub fn constructor(item: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    function::expand_constructor(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_snake_case_to_upper_camel_case() {
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        let camel_case = convert_snake_case_to_upper_camel_case(&ident);
+        assert_eq!(camel_case, "MyField");
+
+        let ident = syn::Ident::new("_0", proc_macro2::Span::call_site());
+        let camel_case = convert_snake_case_to_upper_camel_case(&ident);
+        assert_eq!(camel_case, "0");
+
+        let ident = syn::Ident::new_raw("type", proc_macro2::Span::call_site());
+        let camel_case = convert_snake_case_to_upper_camel_case(&ident);
+        assert_eq!(camel_case, "Type");
+    }
+
+    #[test]
+    fn test_field_data_is_optional() {
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        let ty = syn::parse_quote!(Option<i32>);
+
+        let field = FieldData::new(&ident, &ty);
+        assert!(field.is_optional());
+
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        let ty = syn::parse_quote!(i32);
+        let field = FieldData::new(&ident, &ty);
+        assert!(!field.is_optional());
+
+        for ty in [
+            syn::parse_quote!(std::option::Option<i32>),
+            syn::parse_quote!(::std::option::Option<i32>),
+            syn::parse_quote!(core::option::Option<i32>),
+            syn::parse_quote!(::core::option::Option<i32>),
+            syn::parse_quote!(option::Option<i32>),
+        ] {
+            assert!(FieldData::new(&ident, &ty).is_optional());
+        }
+
+        for ty in [
+            syn::parse_quote!(Option::Foo),
+            syn::parse_quote!(Option::Foo<i32>),
+            syn::parse_quote!(my_crate::Option<i32>),
+            syn::parse_quote!(std::Option<i32>),
+            syn::parse_quote!(Option),
+            syn::parse_quote!(Option<i32, i32>),
+        ] {
+            assert!(!FieldData::new(&ident, &ty).is_optional());
+        }
+
+        let ty = syn::parse_quote!(Option<i32>);
+        let mut field = FieldData::new(&ident, &ty);
+        field.options.required = true;
+        assert!(!field.is_optional());
+
+        let ty = syn::parse_quote!(MaybeI32);
+        let mut field = FieldData::new(&ident, &ty);
+        field.options.optional = true;
+        assert!(field.is_optional());
+    }
+
+    #[test]
+    fn test_field_data_may_be_option() {
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        for ty in [
+            syn::parse_quote!(Option<i32>),
+            syn::parse_quote!(MaybeI32),
+            syn::parse_quote!(aliases::MaybeI32),
+            syn::parse_quote!(<Cart as Trait>::Maybe),
+        ] {
+            assert!(FieldData::new(&ident, &ty).may_be_option());
+        }
+
+        for ty in [
+            syn::parse_quote!(i32),
+            syn::parse_quote!(String),
+            syn::parse_quote!(Vec<Option<i32>>),
+            syn::parse_quote!(&'a Option<i32>),
+            syn::parse_quote!((Option<i32>,)),
+            syn::parse_quote!([Option<i32>; 2]),
+        ] {
+            assert!(!FieldData::new(&ident, &ty).may_be_option());
+        }
+    }
+
+    #[test]
+    fn test_field_data_generate_builder_name() {
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        let ty = syn::parse_quote!(i32);
+        let field = FieldData::new(&ident, &ty);
+
+        let ident = syn::Ident::new("my_Field", proc_macro2::Span::call_site());
+        let ty = syn::parse_quote!(i32);
+        let field2 = FieldData::new(&ident, &ty);
+
+        let state_prefix = "MyStructBuilderMissing";
+
+        let mut other_builders = HashMap::new();
+
+        let builder_name = field.generate_builder_name(state_prefix, &mut other_builders);
+        assert_eq!(builder_name.to_string(), "MyStructBuilderMissingMyField");
+
+        let builder_name = field2.generate_builder_name(state_prefix, &mut other_builders);
+        assert_eq!(builder_name.to_string(), "MyStructBuilderMissingMyField1");
+    }
+
+    #[test]
+    fn test_field_data_generate_builder_idempotent() {
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        let ty = syn::parse_quote!(i32);
+
+        let field = FieldData::new(&ident, &ty);
+
+        let state_prefix = "MyStructBuilderMissing";
+
+        let mut other_builders = HashMap::new();
+
+        let builder_name = field.generate_builder_name(state_prefix, &mut other_builders);
+        assert_eq!(builder_name.to_string(), "MyStructBuilderMissingMyField");
+
+        let builder_name = field.generate_builder_name(state_prefix, &mut other_builders);
+        assert_eq!(builder_name.to_string(), "MyStructBuilderMissingMyField");
+    }
+
+    #[test]
+    fn test_generate_marker_field() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct MyStruct<'a, T: Clone, const N: usize> {
+                my_field: &'a [T; N],
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let (decl, init) = generators[0].generate_marker_field();
+        assert_eq!(
+            decl.to_string(),
+            quote! {
+                __better_builder_marker: ::core::marker::PhantomData<fn() -> (&'a (), T,)>,
+            }
+            .to_string()
+        );
+        assert_eq!(
+            init.to_string(),
+            quote! { __better_builder_marker: ::core::marker::PhantomData, }.to_string()
+        );
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct MyStruct<const N: usize> {
+                my_field: [u8; N],
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let (decl, init) = generators[0].generate_marker_field();
+        assert!(decl.is_empty());
+        assert!(init.is_empty());
+    }
+
+    #[test]
+    fn test_field_data_infers_with_into() {
+        let generics: syn::Generics = syn::parse_quote!(<T>);
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+
+        let ty = syn::parse_quote!(String);
+        assert!(FieldData::new(&ident, &ty).infers_with_into(&generics));
+
+        let ty = syn::parse_quote!(Option<u8>);
+        assert!(FieldData::new(&ident, &ty).infers_with_into(&generics));
+
+        let ty = syn::parse_quote!(u8);
+        assert!(!FieldData::new(&ident, &ty).infers_with_into(&generics));
+
+        let ty = syn::parse_quote!(f64);
+        assert!(!FieldData::new(&ident, &ty).infers_with_into(&generics));
+
+        let ty = syn::parse_quote!(T);
+        assert!(!FieldData::new(&ident, &ty).infers_with_into(&generics));
+
+        let ty = syn::parse_quote!(std::collections::HashMap<String, Vec<T>>);
+        assert!(!FieldData::new(&ident, &ty).infers_with_into(&generics));
+    }
+
+    #[test]
+    fn test_field_data_option_inner_type() {
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+
+        let ty = syn::parse_quote!(Option<Vec<u8>>);
+        let expected: syn::Type = syn::parse_quote!(Vec<u8>);
+        assert_eq!(
+            FieldData::new(&ident, &ty).option_inner_type(),
+            Some(&expected)
+        );
+
+        let ty = syn::parse_quote!(Vec<u8>);
+        assert_eq!(FieldData::new(&ident, &ty).option_inner_type(), None);
+
+        let ty = syn::parse_quote!(Option);
+        assert_eq!(FieldData::new(&ident, &ty).option_inner_type(), None);
+    }
+
+    #[test]
+    fn test_field_data_collection_item_type() {
+        use collection::ItemType;
+
+        let ident = syn::Ident::new("my_field", proc_macro2::Span::call_site());
+        let u8_type: syn::Type = syn::parse_quote!(u8);
+        let string_type: syn::Type = syn::parse_quote!(String);
+
+        for ty in [
+            syn::parse_quote!(Vec<u8>),
+            syn::parse_quote!(std::collections::HashSet<u8>),
+            syn::parse_quote!(HashSet<u8, RandomState>),
+            syn::parse_quote!(BTreeSet<u8>),
+        ] {
+            let field = FieldData::new(&ident, &ty);
+            assert!(matches!(
+                field.collection_item_type(),
+                Some(ItemType::Value(item)) if *item == u8_type
+            ));
+        }
+
+        for ty in [
+            syn::parse_quote!(HashMap<String, u8>),
+            syn::parse_quote!(::std::collections::BTreeMap<String, u8>),
+        ] {
+            let field = FieldData::new(&ident, &ty);
+            assert!(matches!(
+                field.collection_item_type(),
+                Some(ItemType::Entry(key, value)) if *key == string_type && *value == u8_type
+            ));
+        }
+
+        for ty in [
+            syn::parse_quote!(String),
+            syn::parse_quote!(Option<Vec<u8>>),
+            syn::parse_quote!(HashMap<String>),
+            syn::parse_quote!(Vec),
+        ] {
+            let field = FieldData::new(&ident, &ty);
+            assert!(field.collection_item_type().is_none());
+        }
+    }
+
+    #[test]
+    fn test_nested_setter() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Car {
+                #[builder(nested)]
+                engine: parts::Engine<T>,
+            }
+        };
+        let output = derive(&input).unwrap().to_string();
+        // The builders of the field type are found through the traits, rather than by name.
+        assert!(output
+            .contains("< parts :: Engine < T > as :: better_builder :: Buildable > :: builder ()"));
+        assert!(output.contains(":: better_builder :: CompleteBuilder :: build (engine"));
+        assert!(!output.contains("BuilderStart"));
+        assert!(!output.contains("BuilderComplete"));
+    }
+
+    #[test]
+    fn test_convert_upper_camel_case_to_snake_case() {
+        for (input, expected) in [
+            ("MyVariant", "my_variant"),
+            ("Ping", "ping"),
+            ("HTTPRequest", "http_request"),
+            ("Version2Update", "version2_update"),
+            ("A", "a"),
+        ] {
+            let ident = syn::Ident::new(input, proc_macro2::Span::call_site());
+            let snake_case = convert_upper_camel_case_to_snake_case(&ident);
+            assert_eq!(snake_case.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_generator_enum_variants() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum Message {
+                Ping,
+                Text { body: String },
+                Pair(u8, u8),
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let names = generators
+            .iter()
+            .map(|generator| {
+                (
+                    generator.final_builder_name().to_string(),
+                    generator.entry_point_name().to_string(),
+                    generator.target_path().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                ("MessagePingBuilder", "ping_builder", "Message :: Ping"),
+                ("MessageTextBuilder", "text_builder", "Message :: Text"),
+                ("MessagePairBuilder", "pair_builder", "Message :: Pair"),
+            ]
+            .map(|(a, b, c)| (a.to_string(), b.to_string(), c.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_generator_setter_names() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(setter_prefix = "with_")]
+            struct Cart {
+                owner: String,
+                r#type: u8,
+                #[builder(setter(name = "note"), strip_option)]
+                comment: Option<String>,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let names = generators[0]
+            .fields()
+            .iter()
+            .flat_map(FieldData::setters)
+            .map(|setter| setter.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["with_owner", "with_type", "note", "maybe_note"]);
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Cart {
+                owner: String,
+                #[builder(setter(name = "owner"))]
+                buyer: String,
+            }
+        };
+        assert!(BetterBuilderGenerator::from_derive_input(&input).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Cart {
+                #[builder(strip_option)]
+                owner: Option<String>,
+                maybe_owner: u8,
+            }
+        };
+        assert!(BetterBuilderGenerator::from_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn test_generator_custom_names() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(
+                name = "CartFactory",
+                constructor = "factory",
+                build_fn = "finish",
+                state_prefix = "CartNeeds",
+            )]
+            struct Cart {
+                owner: String,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let generator = &generators[0];
+        assert_eq!(generator.final_builder_name(), "CartFactory");
+        assert_eq!(generator.entry_point_name(), "factory");
+        assert_eq!(generator.build_fn_name(), "finish");
+        assert_eq!(generator.state_prefix(), "__CartNeeds");
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Cart {
+                owner: String,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let generator = &generators[0];
+        assert_eq!(generator.final_builder_name(), "CartBuilder");
+        assert_eq!(generator.entry_point_name(), "builder");
+        assert_eq!(generator.build_fn_name(), "build");
+        assert_eq!(generator.state_prefix(), "__CartBuilderMissing");
+    }
+
+    #[test]
+    fn test_generator_hidden_states() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            pub struct Cart {
+                owner: String,
+                #[builder(state_alias = "CartWithoutTotal")]
+                total: u32,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let (exposed, hidden) = generators[0].generate_required_builders();
+        let (exposed, hidden) = (exposed.to_string(), hidden.to_string());
+        assert!(exposed.contains("pub struct CartWithoutTotal"));
+        assert!(!exposed.contains("__CartBuilderMissingOwner"));
+        assert!(hidden.contains("pub struct __CartBuilderMissingOwner"));
+
+        let output = derive(&input).unwrap().to_string();
+        assert!(output.contains("const _ : () = {"));
+    }
+
+    #[test]
+    fn test_derive() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(into)]
+            struct Cart {
+                owner: String,
+            }
+        };
+        let output = derive(&input).unwrap().to_string();
+        assert!(output.contains("struct CartBuilder"));
+        assert!(output.contains("fn build (self) -> Cart"));
+
+        let output = derive_with_options(&input, &quote! { name = "CartFactory" })
+            .unwrap()
+            .to_string();
+        assert!(output.contains("struct CartFactory"));
+        // Options already set by the attributes cannot be repeated.
+        assert!(derive_with_options(&input, &quote! { into }).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            union Cart {
+                owner: u32,
+            }
+        };
+        assert!(derive(&input).is_err());
+    }
+
+    #[test]
+    fn test_trait_impls() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Cart {
+                owner: String,
+            }
+        };
+        let output = derive(&input).unwrap().to_string();
+        assert!(output.contains("impl :: better_builder :: Buildable for Cart"));
+        assert!(output.contains("impl :: better_builder :: CompleteBuilder for CartBuilder"));
+
+        let output = derive_with_options(&input, &quote! { crate = "facade::builder" })
+            .unwrap()
+            .to_string();
+        assert!(output.contains("impl facade :: builder :: Buildable for Cart"));
+        assert!(!output.contains(":: better_builder"));
+
+        // Each variant has its own builder, so the enum is not `Buildable`.
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum Shape {
+                Circle { radius: u8 },
+            }
+        };
+        let output = derive(&input).unwrap().to_string();
+        assert!(!output.contains("Buildable"));
+        assert!(output.contains("CompleteBuilder for ShapeCircleBuilder"));
+
+        // The method calling an `async` function cannot be called through a trait.
+        let item: syn::Item = syn::parse_quote! {
+            async fn fetch(url: String) {}
+        };
+        let output = builder(quote! {}, item).unwrap().to_string();
+        assert!(!output.contains("CompleteBuilder"));
+    }
+
+    #[test]
+    fn test_generator_to_builder() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Cart {
+                owner: String,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let output = generators[0].generate_to_builder(&quote! { CartBuilder });
+        assert!(output.to_string().contains("fn to_builder"));
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum Message {
+                Text { body: String },
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let output = generators[0].generate_to_builder(&quote! { MessageTextBuilder });
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_generator_validated() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Config {
+                #[builder(validate = valid_port)]
+                port: u16,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let generator = &generators[0];
+        assert!(generator.is_validated());
+        assert_eq!(generator.build_fn_name(), "try_build");
+        assert_eq!(generator.error_name(), "ConfigBuilderError");
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[builder(validate = Config::validate, build_fn = "finish", name = "ConfigFactory")]
+            struct Config {
+                port: u16,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let generator = &generators[0];
+        assert!(generator.is_validated());
+        assert_eq!(generator.build_fn_name(), "finish");
+        assert_eq!(generator.error_name(), "ConfigFactoryError");
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Config {
+                port: u16,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        assert!(!generators[0].is_validated());
+    }
+
+    #[test]
+    fn test_generator_skipped() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Request<T> {
+                url: String,
+                #[builder(skip = 3)]
+                attempts: u32,
+                marker: PhantomData<T>,
+                #[builder(optional)]
+                settable: PhantomData<T>,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let generator = &generators[0];
+        let names = |fields: &[FieldData]| {
+            fields
+                .iter()
+                .map(|field| field.ident.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&generator.fields), ["url", "settable"]);
+        assert_eq!(names(&generator.skipped), ["attempts", "marker"]);
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Request {
+                #[builder(skip, default)]
+                attempts: u32,
+            }
+        };
+        assert!(BetterBuilderGenerator::from_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn test_function_builder() {
+        let item: syn::Item = syn::parse_quote! {
+            fn trim(#[builder(into)] text: &str, count: Option<usize>) -> &str {
+                text.trim()
+            }
+        };
+        let output = function::expand(quote! {}, item).unwrap().to_string();
+        assert!(output.contains("fn trim_builder"));
+        assert!(output.contains("struct TrimBuilder < '__better_builder_0 >"));
+        assert!(output.contains("fn call (self) -> & '__better_builder_0 str"));
+        // The options of the arguments are removed from the function itself.
+        assert!(output.contains("fn trim (text : & str , count : Option < usize >)"));
+
+        let item: syn::Item = syn::parse_quote! {
+            fn connect(&self, host: String) {}
+        };
+        assert!(function::expand(quote! {}, item).is_err());
+
+        let item: syn::Item = syn::parse_quote! {
+            impl Display for Client {}
+        };
+        assert!(function::expand(quote! {}, item).is_err());
+
+        let item: syn::Item = syn::parse_quote! {
+            struct Client;
+        };
+        assert!(function::expand(quote! {}, item).is_err());
+
+        let item: syn::Item = syn::parse_quote! {
+            impl Client {
+                #[better_builder::constructor]
+                fn new(name: String) -> Self {
+                    Self { name }
+                }
+            }
+        };
+        let output = function::expand(quote! {}, item).unwrap().to_string();
+        assert!(output.contains("fn builder () -> __ClientBuilderMissingName"));
+        assert!(output.contains("fn build (self) -> Client"));
+
+        // Only a single constructor is allowed, and it cannot take `self`.
+        let item: syn::Item = syn::parse_quote! {
+            impl Client {
+                #[constructor]
+                fn new(name: String) -> Self {}
+                #[constructor]
+                fn with_name(name: String) -> Self {}
+            }
+        };
+        assert!(function::expand(quote! {}, item).is_err());
+        let item: syn::Item = syn::parse_quote! {
+            impl Client {
+                #[constructor]
+                fn new(self, name: String) -> Self {}
+            }
+        };
+        assert!(function::expand(quote! {}, item).is_err());
+    }
+
+    #[test]
+    fn test_setter_docs() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Cart {
+                /// The owner of the cart.
+                owner: String,
+                #[builder(doc = "Sets the discount.")]
+                /// The discount, as a percentage.
+                discount: Option<u8>,
+                total: u32,
+            }
+        };
+        let generators = BetterBuilderGenerator::from_derive_input(&input).unwrap();
+        let fields = &generators[0].fields;
+        let docs = |name: &str| {
+            let field = fields.iter().find(|field| field.ident == name).unwrap();
+            field.setter_docs().to_string()
+        };
+
+        assert_eq!(
+            docs("owner"),
+            quote! { #[doc = r" The owner of the cart."] }.to_string()
+        );
+        assert_eq!(
+            docs("discount"),
+            quote! { #[doc = "Sets the discount."] }.to_string()
+        );
+        assert_eq!(
+            docs("total"),
+            quote! { #[doc = " Sets `total`."] }.to_string()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Code generation for `#[builder(nested)]`, where a field is set by configuring its own builder.
+//!
+//! A nested setter takes a closure from the builder of the field type, created through
+//! `Buildable::builder`, to its complete builder, which is finished through
+//! `CompleteBuilder::build`. The traits resolve the builders of the field type, so it may rename
+//! its entry point or build method. If the field type is validated, building it returns a
+//! `Result`, and the setter returns a `Result` of the next builder through `BuildOutput`.
+
+use quote::quote;
+
+use crate::{FieldData, Setter};
+
+impl Setter {
+    /// Generates the setter method for `field`, taking `receiver` and returning `output`, which is
+    /// built by `body` using the value of the setter.
+    ///
+    /// A nested setter first builds the value from the closure it takes, so `body` is only
+    /// evaluated once the value is available.
+    pub fn generate(
+        &self,
+        field: &FieldData,
+        receiver: &proc_macro2::TokenStream,
+        output: &proc_macro2::TokenStream,
+        body: &proc_macro2::TokenStream,
+        crate_path: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Self {
+            name,
+            argument_type,
+            nested,
+            ..
+        } = self;
+        let field_name = &field.ident;
+        let docs = field.setter_docs();
+
+        if !nested {
+            return quote! {
+                #docs
+                pub fn #name(#receiver, #field_name: #argument_type) -> #output {
+                    #body
+                }
+            };
+        }
+
+        let buildable = quote! { <#argument_type as #crate_path::Buildable> };
+        let built = quote! {
+            <#buildable::Complete as #crate_path::CompleteBuilder>::Output
+        };
+        let build_output = quote! { <#built as #crate_path::BuildOutput<#argument_type, #output>> };
+        quote! {
+            #docs
+            pub fn #name(
+                #receiver,
+                #field_name: impl ::core::ops::FnOnce(#buildable::Builder) -> #buildable::Complete,
+            ) -> #build_output::Mapped {
+                let #field_name =
+                    #crate_path::CompleteBuilder::build(#field_name(#buildable::builder()));
+                #build_output::map(#field_name, move |#field_name| { #body })
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Code generation for `#[builder(skip)]`, where a field has no setter and is only initialised when
+//! the struct is built.
+//!
+//! Skipped fields are kept apart from the other fields of the generator, so they have no slot in
+//! any builder. `PhantomData` fields carry no value, so they are skipped unless an option
+//! configuring their setter is set.
+
+use quote::quote;
+
+use crate::{attributes::FieldDefault, BetterBuilderGenerator, FieldData};
+
+impl<'a> FieldData<'a> {
+    /// Checks if the field holds a [`PhantomData`](core::marker::PhantomData), recognised by the
+    /// last segment of its path.
+    pub fn is_phantom_data(&self) -> bool {
+        let syn::Type::Path(syn::TypePath { qself: None, path }) = self.ty else {
+            return false;
+        };
+        path.segments
+            .last()
+            .map_or(false, |segment| segment.ident == "PhantomData")
+    }
+
+    /// Checks if any option configuring the setter of the field is set, which cannot be combined
+    /// with `#[builder(skip)]`.
+    pub const fn has_setter_options(&self) -> bool {
+        let options = &self.options;
+        options.required
+            || options.optional
+            || options.default.is_some()
+            || options.into
+            || options.strip_option
+            || options.setter_name.is_some()
+            || options.validate.is_some()
+            || options.each.is_some()
+            || options.nested
+            || options.state_alias.is_some()
+            || options.doc.is_some()
+    }
+
+    /// Generates the expression initialising a skipped field when the struct is built.
+    pub fn skip_value(&self) -> proc_macro2::TokenStream {
+        match &self.options.skip {
+            Some(FieldDefault::Expr(expr)) => quote! { #expr },
+            Some(FieldDefault::Trait) | None => quote! { ::core::default::Default::default() },
+        }
+    }
+}
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// Generates the initialisers of the skipped fields, for use in the struct literal of the
+    /// build method.
+    pub fn generate_skipped_values(&self) -> proc_macro2::TokenStream {
+        let members = self.skipped.iter().map(|field| &field.member);
+        let values = self.skipped.iter().map(FieldData::skip_value);
+        quote! { #(#members: #values,)* }
+    }
+}
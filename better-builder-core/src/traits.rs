@@ -0,0 +1,88 @@
+//! Code generation for the `Buildable` and `CompleteBuilder` traits of `better_builder`, which let
+//! generic code create and finish builders.
+//!
+//! Structs implement `Buildable`, returning the same builder as `builder()`. Enums do not, as each
+//! variant has its own builder. Every complete builder implements `CompleteBuilder` by calling its
+//! build method, except those of `async` or `unsafe` functions and of functions returning an
+//! `impl Trait`.
+//!
+//! The traits are found through `::better_builder`, or through the path set with
+//! `#[builder(crate = "...")]` when the crate is re-exported by another.
+
+use quote::quote;
+
+use crate::BetterBuilderGenerator;
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// The path to `better_builder` in generated code, which is `::better_builder` unless set
+    /// with `#[builder(crate = "...")]`.
+    pub fn crate_path(&self) -> proc_macro2::TokenStream {
+        self.options.crate_path.as_ref().map_or_else(
+            || quote! { ::better_builder },
+            |crate_path| quote! { #crate_path },
+        )
+    }
+
+    /// The type returned by the build method, if it can be called through `CompleteBuilder`.
+    fn build_output(&self) -> Option<proc_macro2::TokenStream> {
+        if let Some(function) = &self.function {
+            return function.output_type();
+        }
+
+        let struct_name = self.struct_name();
+        let (_, ty_generics, _) = self.generics().split_for_impl();
+        if self.is_validated() {
+            let error_name = self.error_name();
+            return Some(quote! {
+                ::core::result::Result<#struct_name #ty_generics, #error_name>
+            });
+        }
+        Some(quote! { #struct_name #ty_generics })
+    }
+
+    /// Generates the implementations of `Buildable` for the struct, whose builder starts as
+    /// `start`, and of `CompleteBuilder` for the `complete` builder.
+    pub fn generate_trait_impls(
+        &self,
+        start: &proc_macro2::TokenStream,
+        complete: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let (impl_generics, ty_generics, where_clause) = self.generics().split_for_impl();
+        let crate_path = self.crate_path();
+
+        let buildable = (self.variant.is_none() && self.function.is_none()).then(|| {
+            let struct_name = self.struct_name();
+            let entry_point_name = self.entry_point_name();
+            quote! {
+                impl #impl_generics #crate_path::Buildable for #struct_name #ty_generics
+                    #where_clause
+                {
+                    type Builder = #start;
+                    type Complete = #complete;
+
+                    fn builder() -> Self::Builder {
+                        Self::#entry_point_name()
+                    }
+                }
+            }
+        });
+
+        let complete_builder = self.build_output().map(|output| {
+            let build_fn_name = self.build_fn_name();
+            quote! {
+                impl #impl_generics #crate_path::CompleteBuilder for #complete #where_clause {
+                    type Output = #output;
+
+                    fn build(self) -> Self::Output {
+                        self.#build_fn_name()
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #buildable
+            #complete_builder
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Code generation for `#[builder(unordered)]`, where required fields may be set in any order.
+//!
+//! Rather than a chain of builders, a single builder is generated with one generic parameter per
+//! required field. Each parameter is either a unit struct marking the field as missing, or a
+//! one-tuple holding the value of the field once it has been set. `build()` is only implemented
+//! once every parameter holds a value.
+
+use std::collections::HashMap;
+
+use quote::quote;
+
+use crate::{convert_snake_case_to_upper_camel_case, BetterBuilderGenerator, FieldData};
+
+/// The typestate of a single required field.
+struct RequiredState<'a, 'b> {
+    field: &'b FieldData<'a>,
+    /// The generic parameter tracking the state of the field.
+    param: syn::Ident,
+    /// The unit struct used while the field is missing, which is declared in the anonymous constant
+    /// unless it is named with `#[builder(state_alias = "...")]`.
+    missing: syn::Ident,
+}
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// Generates the argument list needed to name a type with the generics of the parent struct,
+    /// followed by `extra`.
+    pub fn generic_arguments(
+        &self,
+        extra: impl IntoIterator<Item = proc_macro2::TokenStream>,
+    ) -> proc_macro2::TokenStream {
+        let arguments = self
+            .generics()
+            .params
+            .iter()
+            .map(|param| match param {
+                syn::GenericParam::Lifetime(param) => {
+                    let lifetime = &param.lifetime;
+                    quote! { #lifetime }
+                }
+                syn::GenericParam::Type(syn::TypeParam { ident, .. })
+                | syn::GenericParam::Const(syn::ConstParam { ident, .. }) => quote! { #ident },
+            })
+            .chain(extra);
+        quote! { <#(#arguments),*> }
+    }
+
+    /// Clones the generics of the parent struct, adding the given state parameters.
+    ///
+    /// Defaults are removed from the parent's parameters, as they may not precede the state
+    /// parameters.
+    fn generics_with_states<'b>(
+        &self,
+        params: impl IntoIterator<Item = &'b syn::Ident>,
+    ) -> syn::Generics {
+        let mut generics = self.generics().clone();
+        for param in &mut generics.params {
+            match param {
+                syn::GenericParam::Type(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+        }
+        generics.params.extend(
+            params
+                .into_iter()
+                .map(|param| syn::GenericParam::Type(param.clone().into())),
+        );
+        generics
+    }
+
+    fn required_states(&self) -> Vec<RequiredState<'a, '_>> {
+        let state_prefix = self.state_prefix();
+        let mut other_builders = HashMap::new();
+        let mut other_params = HashMap::new();
+        self.fields
+            .iter()
+            .filter(|field| field.is_required())
+            .map(|field| {
+                let camel_case = convert_snake_case_to_upper_camel_case(&field.ident);
+                let mut param = format!("__{camel_case}");
+                let count = other_params.entry(param.clone()).or_insert(0_u16);
+                if *count > 0 {
+                    param.push_str(&count.to_string());
+                }
+                *count = (*count)
+                    .checked_add(1)
+                    .expect("Overflow in state parameter generation");
+
+                let missing = field.generate_builder_name(&state_prefix, &mut other_builders);
+                RequiredState {
+                    field,
+                    param: syn::Ident::new(&param, field.ident.span()),
+                    missing,
+                }
+            })
+            .collect()
+    }
+
+    /// Generates the unit structs marking each required field as missing.
+    ///
+    /// ## Returns
+    ///
+    /// A tuple of the markers named with `#[builder(state_alias = "...")]`, and of the other
+    /// markers, which are declared in the anonymous constant.
+    fn generate_missing_markers(
+        &self,
+        states: &[RequiredState],
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let builder_attributes = self.generate_builder_attributes();
+        let visibility = self.visibility();
+
+        let (aliased, other): (Vec<_>, Vec<_>) = states
+            .iter()
+            .partition(|state| state.field.options.state_alias.is_some());
+        let markers = |states: Vec<&RequiredState>| {
+            let missing_markers = states.iter().map(|state| &state.missing);
+            let missing_docs = states
+                .iter()
+                .map(|state| Self::missing_marker_docs(state.field));
+            quote! {
+                #(
+                    #missing_docs
+                    #builder_attributes
+                    #visibility struct #missing_markers;
+                )*
+            }
+        };
+        (markers(aliased), markers(other))
+    }
+
+    /// Generates the setters for each required field, which are only implemented while the field is
+    /// missing.
+    fn generate_required_setters(&self, states: &[RequiredState]) -> Vec<proc_macro2::TokenStream> {
+        let builder_name = self.final_builder_name();
+        let (_, marker_init) = self.generate_marker_field();
+        let (_, receiver_init) = self.generate_receiver_field();
+        let where_clause = &self.generics().where_clause;
+        let required_names = states
+            .iter()
+            .map(|state| &state.field.ident)
+            .collect::<Vec<_>>();
+        let optional_names = self.optional_names();
+        let crate_path = self.crate_path();
+
+        states
+            .iter()
+            .enumerate()
+            .map(|(index, state)| {
+                let field_name = &state.field.ident;
+                let field_type = state.field.ty;
+                let missing = &state.missing;
+
+                let other_params = states
+                    .iter()
+                    .filter(|other| other.param != state.param)
+                    .map(|other| &other.param);
+                let setter_generics = self.generics_with_states(other_params);
+                let (setter_impl_generics, _, _) = setter_generics.split_for_impl();
+
+                let with_state = |current: proc_macro2::TokenStream| {
+                    self.generic_arguments(states.iter().enumerate().map(|(other_index, other)| {
+                        if other_index == index {
+                            current.clone()
+                        } else {
+                            let param = &other.param;
+                            quote! { #param }
+                        }
+                    }))
+                };
+                let missing_arguments = with_state(quote! { #missing });
+                let set_arguments = with_state(quote! { (#field_type,) });
+
+                let other_names = required_names.iter().filter(|name| **name != field_name);
+                let setters = state.field.setters().into_iter().map(|setter| {
+                    let value = &setter.value;
+                    let other_names = other_names.clone();
+                    setter.generate(
+                        state.field,
+                        &quote! { self },
+                        &quote! { #builder_name #set_arguments },
+                        &quote! {
+                            #builder_name {
+                                #field_name: (#value,),
+                                #(#other_names: self.#other_names,)*
+                                #(#optional_names: self.#optional_names,)*
+                                #receiver_init
+                                #marker_init
+                            }
+                        },
+                        &crate_path,
+                    )
+                });
+
+                quote! {
+                    impl #setter_impl_generics #builder_name #missing_arguments #where_clause {
+                        #(#setters)*
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Generates a single builder on which required fields may be set in any order.
+    ///
+    /// ## Returns
+    ///
+    /// A tuple of the items which can be named, and of the items declared in the anonymous
+    /// constant, like [`BetterBuilderGenerator::generate_final_builder`].
+    pub fn generate_unordered_builder(
+        &self,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let builder_name = self.final_builder_name();
+        let visibility = self.visibility();
+        let (marker_decl, _) = self.generate_marker_field();
+        let (receiver_decl, _) = self.generate_receiver_field();
+        let (impl_generics, _, where_clause) = self.generics().split_for_impl();
+
+        let states = self.required_states();
+        let required_names = states
+            .iter()
+            .map(|state| &state.field.ident)
+            .collect::<Vec<_>>();
+        let optional_names = self.optional_names();
+        let optional_fields = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_required())
+            .map(FieldData::get_name_and_type);
+
+        let builder_generics = self.generics_with_states(states.iter().map(|state| &state.param));
+        let state_params = states.iter().map(|state| &state.param);
+        let docs = self.unordered_builder_docs();
+
+        let builder_attributes = self.generate_builder_attributes();
+        let (exposed_markers, hidden_markers) = self.generate_missing_markers(&states);
+        let builder = quote! {
+            #exposed_markers
+
+            #docs
+            #builder_attributes
+            #visibility struct #builder_name #builder_generics #where_clause {
+                #(#required_names: #state_params,)*
+                #(#optional_fields)*
+                #receiver_decl
+                #marker_decl
+            }
+        };
+
+        let required_setters = self.generate_required_setters(&states);
+
+        let (builder_impl_generics, _, _) = builder_generics.split_for_impl();
+        let all_arguments = self.generic_arguments(states.iter().map(|state| {
+            let param = &state.param;
+            quote! { #param }
+        }));
+        let optional_setters = self.generate_optional_setters();
+
+        let missing_arguments = self.generic_arguments(states.iter().map(|state| {
+            let missing = &state.missing;
+            quote! { #missing }
+        }));
+        let missing_markers = states.iter().map(|state| &state.missing);
+
+        let set_arguments = self.generic_arguments(states.iter().map(|state| {
+            let field_type = state.field.ty;
+            quote! { (#field_type,) }
+        }));
+        let values = self
+            .fields
+            .iter()
+            .map(|field| {
+                if field.is_required() {
+                    let field_name = &field.ident;
+                    quote! { self.#field_name.0 }
+                } else {
+                    field.build_value()
+                }
+            })
+            .collect::<Vec<_>>();
+        let build_fn = self.generate_build_fn(&values);
+
+        let complete = quote! { #builder_name #set_arguments };
+        let (exposed_entry_point, entry_point) = self.generate_entry_point(
+            &quote! { #builder_name },
+            &missing_arguments,
+            &quote! {
+                #(#required_names: #missing_markers,)*
+                #(#optional_names: None,)*
+            },
+            &complete,
+        );
+        let start = quote! { #builder_name #missing_arguments };
+        let trait_impls = self.generate_trait_impls(&start, &complete);
+        let to_builder = self.generate_to_builder(&complete);
+
+        let exposed = quote! {
+            #builder
+            #exposed_entry_point
+        };
+        let hidden = quote! {
+            #hidden_markers
+
+            #(#required_setters)*
+
+            impl #builder_impl_generics #builder_name #all_arguments #where_clause {
+                #(#optional_setters)*
+            }
+
+            impl #impl_generics #builder_name #set_arguments #where_clause {
+                #build_fn
+            }
+
+            #entry_point
+            #to_builder
+            #trait_impls
+        };
+        (exposed, hidden)
+    }
+}
@@ -0,0 +1,144 @@
+//! Code generation for `#[builder(validate = ...)]`, where the struct is checked as it is built.
+//!
+//! Once the struct or any of its fields has a validator, the build method returns a `Result`. Its
+//! error is a generated enum with a variant for each validated field, and one for the struct
+//! itself, holding the message of the failed validator.
+
+use quote::quote;
+use syn::ext::IdentExt;
+
+use crate::{convert_snake_case_to_upper_camel_case, BetterBuilderGenerator, FieldData};
+
+impl<'a> FieldData<'a> {
+    /// The local variable holding the value of the field while it is validated.
+    ///
+    /// The field name itself is not used, as it could shadow the validator.
+    fn validated_name(&self) -> syn::Ident {
+        quote::format_ident!("__better_builder_{}", self.ident.unraw())
+    }
+
+    /// The variant of the error type reported when the field fails validation.
+    fn error_variant(&self) -> syn::Ident {
+        let field_name = convert_snake_case_to_upper_camel_case(&self.ident);
+        syn::Ident::new(&format!("Invalid{field_name}"), self.ident.span())
+    }
+}
+
+impl<'a> BetterBuilderGenerator<'a> {
+    /// Checks if the struct or any of its fields is validated, making the build method fallible.
+    pub fn is_validated(&self) -> bool {
+        self.options.validate.is_some()
+            || self
+                .fields
+                .iter()
+                .any(|field| field.options.validate.is_some())
+    }
+
+    /// The name of the error returned when validation fails, such as `CartBuilderError`.
+    pub fn error_name(&self) -> syn::Ident {
+        quote::format_ident!("{}Error", self.final_builder_name())
+    }
+
+    /// Generates the error type returned by the build method, if the struct is validated.
+    pub fn generate_error_type(&self) -> proc_macro2::TokenStream {
+        if !self.is_validated() {
+            return quote! {};
+        }
+
+        let error_name = self.error_name();
+        let visibility = self.visibility();
+        let target_name = self.variant.map_or_else(
+            || self.struct_name().to_string(),
+            |variant| format!("{}::{}", self.struct_name(), variant.ident.unraw()),
+        );
+
+        let mut variants = Vec::new();
+        let mut docs = Vec::new();
+        let mut messages = Vec::new();
+        for field in self
+            .fields
+            .iter()
+            .filter(|field| field.options.validate.is_some())
+        {
+            let field_name = field.ident.unraw();
+            variants.push(field.error_variant());
+            docs.push(format!(" The `{field_name}` field failed validation."));
+            messages.push(format!("invalid `{field_name}`: {{}}"));
+        }
+        if self.options.validate.is_some() {
+            variants.push(syn::Ident::new("Invalid", proc_macro2::Span::call_site()));
+            docs.push(format!(" The `{target_name}` failed validation."));
+            messages.push(format!("invalid `{target_name}`: {{}}"));
+        }
+
+        let error_docs = self.error_docs();
+
+        quote! {
+            #error_docs
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #visibility enum #error_name {
+                #(
+                    #[doc = #docs]
+                    #variants(::std::string::String),
+                )*
+            }
+
+            impl ::core::fmt::Display for #error_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(Self::#variants(error) => ::core::write!(f, #messages, error),)*
+                    }
+                }
+            }
+
+            impl ::std::error::Error for #error_name {}
+        }
+    }
+
+    /// Generates the body of a validated build method, where `values` holds the expression taking
+    /// each field out of the builder.
+    pub fn generate_validated_build(
+        &self,
+        values: &[proc_macro2::TokenStream],
+    ) -> proc_macro2::TokenStream {
+        let target_path = self.target_path();
+        let error_name = self.error_name();
+        let members = self.fields.iter().map(|field| &field.member);
+        let names = self
+            .fields
+            .iter()
+            .map(FieldData::validated_name)
+            .collect::<Vec<_>>();
+
+        let field_validations = self.fields.iter().filter_map(|field| {
+            let validate = field.options.validate.as_ref()?;
+            let name = field.validated_name();
+            let variant = field.error_variant();
+            Some(quote! {
+                (#validate)(&#name).map_err(|error| {
+                    #error_name::#variant(::std::string::ToString::to_string(&error))
+                })?;
+            })
+        });
+        let struct_validation = self.options.validate.as_ref().map(|validate| {
+            quote! {
+                (#validate)(&__better_builder_value).map_err(|error| {
+                    #error_name::Invalid(::std::string::ToString::to_string(&error))
+                })?;
+            }
+        });
+
+        let skipped_values = self.generate_skipped_values();
+
+        quote! {
+            #(let #names = #values;)*
+            #(#field_validations)*
+            let __better_builder_value = #target_path {
+                #(#members: #names,)*
+                #skipped_values
+            };
+            #struct_validation
+            ::core::result::Result::Ok(__better_builder_value)
+        }
+    }
+}
@@ -0,0 +1,34 @@
+#![warn(clippy::pedantic, clippy::nursery, clippy::all)]
+
+use proc_macro::TokenStream;
+
+#[proc_macro_derive(BetterBuilder, attributes(builder))]
+pub fn derive_better_builder(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    match better_builder_core::derive(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Generates a builder for the arguments of a function, whose `call()` method invokes it.
+///
+/// On a free function such as `connect`, a `connect_builder()` function creating the builder is
+/// added next to it. On an `impl` block, a builder is generated for each method marked with
+/// `#[builder]`, created by an associated function such as `Client::connect_builder()` or, for
+/// methods taking `self`, a method such as `client.connect_builder()`.
+#[proc_macro_attribute]
+pub fn builder(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item as syn::Item);
+    match better_builder_core::builder(args.into(), item) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Marks an associated function such as `new` inside an `impl` block with
+/// `#[better_builder::builder]`, generating a `builder()` whose `build()` calls the function.
+#[proc_macro_attribute]
+pub fn constructor(_args: TokenStream, item: TokenStream) -> TokenStream {
+    better_builder_core::constructor(&item.into()).into()
+}
@@ -1,8 +1,27 @@
 #[test]
 fn macro_failures() {
     let t = trybuild::TestCases::new();
-    t.compile_fail("tests/compile_tests/should_fail/error_on_enum.rs");
-    t.compile_fail("tests/compile_tests/should_fail/error_on_tuple_struct.rs");
+    t.compile_fail("tests/compile_tests/should_fail/error_on_union.rs");
+    t.compile_fail("tests/compile_tests/should_fail/name_on_named_field.rs");
+    t.compile_fail("tests/compile_tests/should_fail/duplicate_setter.rs");
+    t.compile_fail("tests/compile_tests/should_fail/build_fn_collision.rs");
+    t.compile_fail("tests/compile_tests/should_fail/enum_variant_options.rs");
+    t.compile_fail("tests/compile_tests/should_fail/each_not_collection.rs");
+    t.compile_fail("tests/compile_tests/should_fail/nested_incomplete.rs");
+    t.compile_fail("tests/compile_tests/should_fail/hidden_states.rs");
+    t.compile_fail("tests/compile_tests/should_fail/skip_with_setter_option.rs");
+    t.compile_fail("tests/compile_tests/should_fail/skip_no_setter.rs");
+    t.compile_fail("tests/compile_tests/should_fail/function_impl_trait.rs");
+    t.compile_fail("tests/compile_tests/should_fail/function_cfg_argument.rs");
+    t.compile_fail("tests/compile_tests/should_fail/function_missing_argument.rs");
+    t.compile_fail("tests/compile_tests/should_fail/constructor_outside_impl.rs");
+    t.compile_fail("tests/compile_tests/should_fail/unknown_attribute.rs");
+    t.compile_fail("tests/compile_tests/should_fail/strip_option_not_option.rs");
+    t.compile_fail("tests/compile_tests/should_fail/optional_not_option.rs");
+    t.compile_fail("tests/compile_tests/should_fail/unordered_missing_field.rs");
+    t.compile_fail("tests/compile_tests/should_fail/unordered_set_twice.rs");
+    t.compile_fail("tests/compile_tests/should_fail/required_option_missing.rs");
+    t.compile_fail("tests/compile_tests/should_fail/required_with_default.rs");
 }
 
 #[test]
@@ -11,11 +30,33 @@ fn macro_successes() {
     t.pass("tests/compile_tests/should_pass/basic.rs");
     t.pass("tests/compile_tests/should_pass/all_optional.rs");
     t.pass("tests/compile_tests/should_pass/all_required.rs");
-    // t.pass("tests/compile_tests/should_pass/generics.rs");
+    t.pass("tests/compile_tests/should_pass/generics.rs");
     t.pass("tests/compile_tests/should_pass/duplicates.rs");
-    // t.pass("tests/compile_tests/should_pass/function_collision.rs");
+    t.pass("tests/compile_tests/should_pass/function_collision.rs");
     t.pass("tests/compile_tests/should_pass/no_fields.rs");
-    // t.pass("tests/compile_tests/should_pass/trait_derivation.rs");
+    t.pass("tests/compile_tests/should_pass/defaults.rs");
+    t.pass("tests/compile_tests/should_pass/into.rs");
+    t.pass("tests/compile_tests/should_pass/strip_option.rs");
+    t.pass("tests/compile_tests/should_pass/unordered.rs");
+    t.pass("tests/compile_tests/should_pass/option_detection.rs");
+    t.pass("tests/compile_tests/should_pass/enums.rs");
+    t.pass("tests/compile_tests/should_pass/tuple_structs.rs");
+    t.pass("tests/compile_tests/should_pass/setter_names.rs");
+    t.pass("tests/compile_tests/should_pass/validate.rs");
+    t.pass("tests/compile_tests/should_pass/each.rs");
+    t.pass("tests/compile_tests/should_pass/nested.rs");
+    t.pass("tests/compile_tests/should_pass/to_builder.rs");
+    t.pass("tests/compile_tests/should_pass/trait_derivation.rs");
+    t.pass("tests/compile_tests/should_pass/hidden_states.rs");
+    t.pass("tests/compile_tests/should_pass/local_scope.rs");
+    t.pass("tests/compile_tests/should_pass/docs.rs");
+    t.pass("tests/compile_tests/should_pass/cfg_fields.rs");
+    t.pass("tests/compile_tests/should_pass/skip.rs");
+    t.pass("tests/compile_tests/should_pass/declaration_order.rs");
+    t.pass("tests/compile_tests/should_pass/functions.rs");
+    t.pass("tests/compile_tests/should_pass/constructors.rs");
+    t.pass("tests/compile_tests/should_pass/traits.rs");
+    t.pass("tests/compile_tests/should_pass/crate_path.rs");
 
     // TODO: add tests for feature flags in the future;
     // https://github.com/dtolnay/trybuild/issues/108
@@ -0,0 +1,11 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+#[builder(build_fn = "finish")]
+struct Cart {
+    owner: String,
+    #[builder(default)]
+    finish: bool,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+struct Connection {
+    address: String,
+}
+
+impl Connection {
+    #[better_builder::constructor]
+    fn new(address: String) -> Self {
+        Self { address }
+    }
+}
+
+fn main() {
+    let _ = Connection::builder().address("localhost".to_string()).build();
+}
@@ -0,0 +1,10 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    owner: String,
+    #[builder(setter(name = "owner"))]
+    buyer: String,
+}
+
+fn main() {}
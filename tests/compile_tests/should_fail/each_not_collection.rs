@@ -0,0 +1,16 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    #[builder(each = "item")]
+    owner: String,
+}
+
+#[derive(BetterBuilder)]
+struct Order {
+    #[builder(each = "item")]
+    items: Vec<String>,
+    item: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+#[builder(name = "MessageBuilder")]
+enum Message {
+    Ping,
+    Pong,
+}
+
+fn main() {}
@@ -1,11 +1,9 @@
 use better_builder::BetterBuilder;
 
 #[derive(BetterBuilder)]
-struct Cart(
-    String,
-    u8,
-    Option<u8>,
-    Vec<String>,
-);
+union Cart {
+    num_wheels: u8,
+    num_seats: u16,
+}
 
 fn main() {}
@@ -0,0 +1,6 @@
+#[better_builder::builder]
+fn sum(#[cfg(any())] a: u8, b: u8) -> u8 {
+    b
+}
+
+fn main() {}
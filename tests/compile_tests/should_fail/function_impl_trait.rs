@@ -0,0 +1,6 @@
+#[better_builder::builder]
+fn greet(name: impl Into<String>) -> String {
+    name.into()
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+struct Client;
+
+#[better_builder::builder]
+impl Client {
+    #[builder]
+    fn connect(&self, host: String, port: u16) -> String {
+        format!("{host}:{port}")
+    }
+}
+
+fn main() {
+    let _ = Client.connect_builder().host("localhost".to_string()).call();
+}
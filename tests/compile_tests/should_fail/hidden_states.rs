@@ -0,0 +1,23 @@
+mod shop {
+    use better_builder::BetterBuilder;
+
+    #[derive(BetterBuilder)]
+    pub struct Cart {
+        pub owner: String,
+        pub total: u32,
+    }
+
+    #[derive(BetterBuilder)]
+    #[builder(unordered)]
+    pub struct Order {
+        pub item: String,
+    }
+}
+
+use shop::*;
+
+fn main() {
+    let _state: __CartBuilderMissingOwner = Cart::builder();
+    let _state: shop::__CartBuilderMissingTotal = shop::Cart::builder().owner(String::new());
+    let _state: OrderBuilder<__OrderBuilderMissingItem> = Order::builder();
+}
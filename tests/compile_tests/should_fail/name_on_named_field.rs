@@ -0,0 +1,9 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    #[builder(name = "buyer")]
+    owner: String,
+}
+
+fn main() {}
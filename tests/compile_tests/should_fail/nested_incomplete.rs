@@ -0,0 +1,17 @@
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Engine {
+    cylinders: u8,
+    turbo: bool,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Car {
+    #[builder(nested)]
+    engine: Engine,
+}
+
+fn main() {
+    let _car = Car::builder().engine(|e| e.cylinders(4)).build();
+}
@@ -0,0 +1,9 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    #[builder(optional)]
+    owner: String,
+}
+
+fn main() {}
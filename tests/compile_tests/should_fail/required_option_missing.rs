@@ -0,0 +1,12 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    owner: String,
+    #[builder(required)]
+    nickname: Option<String>,
+}
+
+fn main() {
+    let _cart = Cart::builder().owner("Alice".to_string()).build();
+}
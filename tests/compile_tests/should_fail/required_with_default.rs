@@ -0,0 +1,9 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    #[builder(required, default)]
+    nickname: Option<String>,
+}
+
+fn main() {}
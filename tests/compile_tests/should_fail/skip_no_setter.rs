@@ -0,0 +1,12 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Request {
+    url: String,
+    #[builder(skip)]
+    attempts: u32,
+}
+
+fn main() {
+    let _ = Request::builder().url(String::new()).attempts(3).build();
+}
@@ -0,0 +1,10 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Request {
+    url: String,
+    #[builder(skip, into)]
+    attempts: u32,
+}
+
+fn main() {}
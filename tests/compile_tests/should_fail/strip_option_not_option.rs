@@ -0,0 +1,9 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    #[builder(strip_option, default)]
+    owner: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+struct Cart {
+    #[builder(not_an_option)]
+    owner: String,
+}
+
+fn main() {}
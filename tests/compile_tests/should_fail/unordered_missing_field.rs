@@ -0,0 +1,13 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+#[builder(unordered)]
+struct Cart {
+    owner: String,
+    num_wheels: u8,
+    num_seats: Option<u8>,
+}
+
+fn main() {
+    let _cart = Cart::builder().num_seats(Some(2)).owner("Alice".to_string()).build();
+}
@@ -0,0 +1,16 @@
+use better_builder::BetterBuilder;
+
+#[derive(BetterBuilder)]
+#[builder(unordered)]
+struct Cart {
+    owner: String,
+    num_wheels: u8,
+}
+
+fn main() {
+    let _cart = Cart::builder()
+        .owner("Alice".to_string())
+        .num_wheels(4)
+        .owner("Bob".to_string())
+        .build();
+}
@@ -0,0 +1,45 @@
+//! This test checks that fields and builder options disabled with `#[cfg]` and `#[cfg_attr]` are
+//! left out of the builder. `cfg(all())` is always enabled and `cfg(any())` never is.
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Connection {
+    host: String,
+    #[cfg(any())]
+    certificate: String,
+    #[cfg(all())]
+    user: String,
+    #[cfg_attr(all(), builder(default = 443))]
+    port: u16,
+    #[cfg_attr(any(), builder(default))]
+    timeout: u32,
+    #[cfg(any())]
+    proxy: Option<String>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered)]
+struct Session {
+    #[cfg(any())]
+    token: String,
+    id: u64,
+    #[cfg(all())]
+    label: Option<String>,
+}
+
+fn main() {
+    let connection = Connection::builder()
+        .host("localhost".to_string())
+        .user("admin".to_string())
+        .timeout(30)
+        .build();
+    assert_eq!(connection.host, "localhost");
+    assert_eq!(connection.user, "admin");
+    assert_eq!(connection.port, 443);
+    assert_eq!(connection.timeout, 30);
+
+    let session = Session::builder().id(1).label(Some("main".to_string())).build();
+    assert_eq!(session.id, 1);
+    assert_eq!(session.label.as_deref(), Some("main"));
+}
@@ -0,0 +1,90 @@
+//! This test checks builders generated for constructors with `#[better_builder::constructor]`.
+
+use std::{fmt, marker::PhantomData};
+
+#[derive(Debug)]
+struct Connection {
+    address: String,
+    retries: u8,
+    timeout: u32,
+}
+
+#[better_builder::builder]
+impl Connection {
+    const TIMEOUT: u32 = 30;
+
+    /// Connects to a host, computing its address.
+    #[better_builder::constructor]
+    fn new(
+        host: &str,
+        #[builder(default = 80)] port: u16,
+        retries: Option<u8>,
+        #[builder(skip = Self::TIMEOUT)] timeout: u32,
+    ) -> Self {
+        Self {
+            address: format!("{host}:{port}"),
+            retries: retries.unwrap_or(3),
+            timeout,
+        }
+    }
+
+    #[builder]
+    fn with_port(port: u16) -> Self {
+        Self::new("localhost", port, None, Self::TIMEOUT)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct PortError(u16);
+
+impl fmt::Display for PortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid port {}", self.0)
+    }
+}
+
+#[derive(Debug)]
+struct Port<T> {
+    number: u16,
+    protocol: PhantomData<T>,
+}
+
+#[derive(Debug)]
+struct Tcp;
+
+#[better_builder::builder]
+impl<T> Port<T> {
+    #[better_builder::constructor(unordered, constructor = "open")]
+    fn try_new(number: u16, reserved: bool) -> Result<Self, PortError> {
+        if reserved && number < 1024 {
+            return Err(PortError(number));
+        }
+        Ok(Self {
+            number,
+            protocol: PhantomData,
+        })
+    }
+}
+
+fn main() {
+    let connection = Connection::builder()
+        .host("example.com")
+        .retries(Some(5))
+        .build();
+    assert_eq!(connection.address, "example.com:80");
+    assert_eq!(connection.retries, 5);
+    assert_eq!(connection.timeout, 30);
+
+    let connection = Connection::with_port_builder().port(8080).call();
+    assert_eq!(connection.address, "localhost:8080");
+
+    let port = Port::<Tcp>::open()
+        .reserved(false)
+        .number(80)
+        .build()
+        .unwrap();
+    assert_eq!(port.number, 80);
+
+    let error = Port::<Tcp>::open().number(80).reserved(true).build();
+    assert_eq!(error.unwrap_err(), PortError(80));
+}
@@ -0,0 +1,36 @@
+//! This test checks that `#[builder(crate = "...")]` sets the path to `better_builder` used by
+//! generated code, as needed when it is re-exported by another crate.
+
+mod facade {
+    pub use better_builder::*;
+}
+
+use facade::{BetterBuilder, Buildable, CompleteBuilder};
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(crate = "crate::facade")]
+struct Cart {
+    owner: String,
+    discount: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(crate = "crate::facade")]
+enum Shape {
+    Circle { radius: u8 },
+}
+
+#[better_builder::builder(crate = "crate::facade")]
+fn area(width: u8, height: u8) -> u16 {
+    u16::from(width) * u16::from(height)
+}
+
+fn main() {
+    let cart = <Cart as Buildable>::builder().owner("Alice".to_string()).build();
+    assert_eq!(cart.discount, None);
+
+    let shape = CompleteBuilder::build(Shape::circle_builder().radius(1));
+    assert_eq!(shape, Shape::Circle { radius: 1 });
+
+    assert_eq!(CompleteBuilder::build(area_builder().width(2).height(3)), 6);
+}
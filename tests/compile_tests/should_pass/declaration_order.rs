@@ -0,0 +1,66 @@
+//! This test checks that optional fields may be set on every intermediate state, so fields can be
+//! set in the order they are declared.
+
+use std::marker::PhantomData;
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart {
+    owner: String,
+    num_seats: Option<u8>,
+    #[builder(default = 4)]
+    num_wheels: u8,
+    color: String,
+    #[builder(each = "item")]
+    inventory: Vec<String>,
+    price: u32,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Tagged<'a, T> {
+    label: Option<&'a str>,
+    value: T,
+    #[builder(strip_option)]
+    note: Option<String>,
+    marker: PhantomData<T>,
+}
+
+fn main() {
+    let cart = Cart::builder()
+        .num_seats(Some(2))
+        .owner("Alice".to_string())
+        .num_wheels(3)
+        .item("apple".to_string())
+        .color("red".to_string())
+        .item("banana".to_string())
+        .price(10)
+        .build();
+    assert_eq!(cart.owner, "Alice");
+    assert_eq!(cart.num_seats, Some(2));
+    assert_eq!(cart.num_wheels, 3);
+    assert_eq!(cart.color, "red");
+    assert_eq!(cart.inventory, vec!["apple".to_string(), "banana".to_string()]);
+    assert_eq!(cart.price, 10);
+
+    // Optional fields set early can still be overwritten later on.
+    let cart = Cart::builder()
+        .num_seats(Some(2))
+        .owner("Bob".to_string())
+        .color("blue".to_string())
+        .price(5)
+        .num_seats(None)
+        .build();
+    assert_eq!(cart.num_seats, None);
+    assert_eq!(cart.num_wheels, 4);
+    assert!(cart.inventory.is_empty());
+
+    let tagged = Tagged::builder()
+        .label(Some("answer"))
+        .value(42)
+        .note("checked".to_string())
+        .build();
+    assert_eq!(tagged.label, Some("answer"));
+    assert_eq!(tagged.value, 42);
+    assert_eq!(tagged.note.as_deref(), Some("checked"));
+}
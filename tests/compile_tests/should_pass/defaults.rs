@@ -0,0 +1,87 @@
+//! This test checks that fields with a `#[builder(default)]` attribute are optional and are filled
+//! in when the builder is built.
+
+use std::time::Duration;
+
+use better_builder::BetterBuilder;
+
+const DEFAULT_PORT: u16 = 8080;
+
+fn default_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Config {
+    host: String,
+    #[builder(default = DEFAULT_PORT)]
+    port: u16,
+    #[builder(default = default_timeout())]
+    timeout: Duration,
+    #[builder(default)]
+    retries: u32,
+    #[builder(default)]
+    aliases: Vec<String>,
+    #[builder(default = Some(3))]
+    workers: Option<u8>,
+    proxy: Option<String>,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Generic<T: Default> {
+    #[builder(default)]
+    value: T,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Server<T> {
+    name: T,
+    #[builder(default = Self::DEFAULT_PORT)]
+    port: u16,
+    #[builder(default = Self::default_hosts(), each = "host")]
+    hosts: Vec<String>,
+}
+
+impl<T> Server<T> {
+    const DEFAULT_PORT: u16 = 8080;
+
+    fn default_hosts() -> Vec<String> {
+        vec!["localhost".to_string()]
+    }
+}
+
+fn main() {
+    let t = Config::builder().host("localhost".to_string()).build();
+    assert_eq!(t.host, "localhost");
+    assert_eq!(t.port, 8080);
+    assert_eq!(t.timeout, Duration::from_secs(30));
+    assert_eq!(t.retries, 0);
+    assert!(t.aliases.is_empty());
+    assert_eq!(t.workers, Some(3));
+    assert_eq!(t.proxy, None);
+
+    let t = Config::builder()
+        .host("localhost".to_string())
+        .retries(5)
+        .port(443)
+        .workers(None)
+        .timeout(Duration::from_secs(1))
+        .aliases(vec!["example.com".to_string()])
+        .build();
+    assert_eq!(t.port, 443);
+    assert_eq!(t.timeout, Duration::from_secs(1));
+    assert_eq!(t.retries, 5);
+    assert_eq!(t.aliases, vec!["example.com".to_string()]);
+    assert_eq!(t.workers, None);
+
+    let t = Generic::<u64>::builder().build();
+    assert_eq!(t.value, 0);
+    let t = Generic::builder().value("value").build();
+    assert_eq!(t.value, "value");
+
+    let t = Server::builder().name("main").build();
+    assert_eq!(t.port, 8080);
+    assert_eq!(t.hosts, ["localhost"]);
+    let t = Server::builder().name(1).host("example.com".to_string()).build();
+    assert_eq!(t.hosts, ["localhost", "example.com"]);
+}
@@ -0,0 +1,61 @@
+//! This test checks that every generated builder and method is documented.
+#![deny(missing_docs)]
+
+use std::collections::HashMap;
+
+use better_builder::BetterBuilder;
+
+/// A shopping cart.
+#[derive(Debug, BetterBuilder)]
+pub struct Cart {
+    /// The owner of the cart.
+    pub owner: String,
+    #[builder(doc = "Sets the prices of the items in the cart.")]
+    /// The prices of the items, by name.
+    pub prices: HashMap<String, u32>,
+    #[builder(each = "tag")]
+    /// Tags attached to the cart.
+    pub tags: Vec<String>,
+    discount: Option<u8>,
+}
+
+/// A validated order.
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered, doc = "A builder for orders, in any order.")]
+pub struct Order {
+    /// The quantity ordered.
+    #[builder(validate = |quantity: &u32| if *quantity > 0 { Ok(()) } else { Err("empty") })]
+    pub quantity: u32,
+    item: String,
+    /// A note for the seller.
+    pub note: Option<String>,
+}
+
+/// A message sent to the shop.
+#[derive(Debug, BetterBuilder)]
+pub enum Message {
+    /// Checks the shop is alive.
+    Ping {
+        /// The time the ping was sent.
+        sent: u64,
+    },
+}
+
+fn main() {
+    let cart = Cart::builder()
+        .owner("Alice".to_string())
+        .prices(HashMap::new())
+        .tag("gift".to_string())
+        .build();
+    assert_eq!(cart.tags, vec!["gift".to_string()]);
+
+    let order = Order::builder()
+        .item("book".to_string())
+        .quantity(2)
+        .try_build()
+        .unwrap();
+    assert_eq!(order.quantity, 2);
+
+    let Message::Ping { sent } = Message::ping_builder().sent(3).build();
+    assert_eq!(sent, 3);
+}
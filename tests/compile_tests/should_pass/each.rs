@@ -0,0 +1,67 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart {
+    owner: String,
+    #[builder(each = "item")]
+    inventory: Vec<String>,
+    #[builder(each = "tag")]
+    tags: HashSet<String>,
+    #[builder(each = "price")]
+    prices: HashMap<String, u32>,
+    #[builder(each = "discount", default = vec![5])]
+    discounts: Vec<u8>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered)]
+struct Queue {
+    name: String,
+    #[builder(each = "job")]
+    jobs: VecDeque<u32>,
+    #[builder(each = "label")]
+    labels: std::collections::BTreeMap<u8, &'static str>,
+    #[builder(each = "priority")]
+    priorities: BTreeSet<u8>,
+}
+
+fn main() {
+    let cart = Cart::builder()
+        .owner("Alice".to_string())
+        .item("apple".to_string())
+        .item("banana".to_string())
+        .extend_inventory(vec!["cherry".to_string()])
+        .tag("fruit".to_string())
+        .price("apple".to_string(), 3)
+        .extend_prices([("banana".to_string(), 2)])
+        .discount(10)
+        .build();
+    assert_eq!(cart.inventory, vec!["apple", "banana", "cherry"]);
+    assert!(cart.tags.contains("fruit"));
+    assert_eq!(cart.prices["apple"], 3);
+    assert_eq!(cart.prices["banana"], 2);
+    assert_eq!(cart.discounts, vec![5, 10]);
+
+    let cart = Cart::builder()
+        .owner("Bob".to_string())
+        .inventory(vec!["pear".to_string()])
+        .item("plum".to_string())
+        .build();
+    assert_eq!(cart.inventory, vec!["pear", "plum"]);
+    assert!(cart.tags.is_empty());
+    assert_eq!(cart.discounts, vec![5]);
+
+    let queue = Queue::builder()
+        .job(1)
+        .name("main".to_string())
+        .extend_jobs([2, 3])
+        .label(1, "urgent")
+        .priority(2)
+        .build();
+    assert_eq!(queue.jobs, VecDeque::from([1, 2, 3]));
+    assert_eq!(queue.labels, BTreeMap::from([(1, "urgent")]));
+    assert_eq!(queue.priorities, BTreeSet::from([2]));
+    assert_eq!(queue.name, "main");
+}
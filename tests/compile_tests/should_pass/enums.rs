@@ -0,0 +1,87 @@
+//! This test checks that a builder is generated for each variant of an enum.
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+enum Cart {
+    Owner(String),
+    NumWheels(u8),
+    NumSeats(u8),
+    Inventory(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+enum Message<'a, T> {
+    Ping,
+    Text {
+        sender: &'a str,
+        body: String,
+        #[builder(default)]
+        urgent: bool,
+        reply_to: Option<u64>,
+    },
+    Payload(T, Option<u32>),
+    HTTPRequest {
+        url: String,
+    },
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(unordered, strip_option)]
+enum Shape {
+    Rectangle { width: u32, height: u32 },
+    Circle { radius: u32, label: Option<String> },
+}
+
+fn main() {
+    let t = Cart::owner_builder()._0("Alice".to_string()).build();
+    assert_eq!(t, Cart::Owner("Alice".to_string()));
+    let t = Cart::num_wheels_builder()._0(4).build();
+    assert_eq!(t, Cart::NumWheels(4));
+    let t = Cart::num_seats_builder()._0(2).build();
+    assert_eq!(t, Cart::NumSeats(2));
+    let t = Cart::inventory_builder()._0(vec!["apple".to_string()]).build();
+    assert_eq!(t, Cart::Inventory(vec!["apple".to_string()]));
+
+    let t = Message::<()>::ping_builder().build();
+    assert_eq!(t, Message::Ping);
+
+    let t = Message::<()>::text_builder()
+        .sender("Alice")
+        .body("Hello".to_string())
+        .reply_to(Some(1))
+        .build();
+    assert_eq!(
+        t,
+        Message::Text {
+            sender: "Alice",
+            body: "Hello".to_string(),
+            urgent: false,
+            reply_to: Some(1),
+        }
+    );
+
+    let t = Message::payload_builder()._0([1u8, 2, 3])._1(Some(3)).build();
+    assert_eq!(t, Message::Payload([1, 2, 3], Some(3)));
+
+    let t = Message::<()>::http_request_builder()
+        .url("https://example.com".to_string())
+        .build();
+    assert_eq!(
+        t,
+        Message::HTTPRequest {
+            url: "https://example.com".to_string()
+        }
+    );
+
+    let t = Shape::rectangle_builder().height(2).width(3).build();
+    assert_eq!(t, Shape::Rectangle { width: 3, height: 2 });
+    let t = Shape::circle_builder().label("round".to_string()).radius(1).build();
+    assert_eq!(
+        t,
+        Shape::Circle {
+            radius: 1,
+            label: Some("round".to_string())
+        }
+    );
+}
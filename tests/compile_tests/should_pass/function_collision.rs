@@ -1,14 +1,29 @@
-//! This test checks that the macro works even with function collisions.
+//! This test checks that the macro works even with function collisions, by renaming the generated
+//! builder, constructor and intermediate states.
 
 use better_builder::BetterBuilder;
 
 #[derive(Debug, BetterBuilder)]
+#[builder(
+    name = "CartFactory",
+    constructor = "factory",
+    build_fn = "finish",
+    state_prefix = "CartNeeds"
+)]
 struct Cart {
+    #[builder(state_alias = "CartWithoutOwner")]
     owner: String,
     num_wheels: u8,
     num_seats: Option<u8>,
 }
 
+// Create a type that we expect to collide!
+struct CartBuilder;
+
+// And types that would collide with the intermediate states, if they were not hidden.
+struct CartBuilderMissingOwner;
+struct CartNeedsOwner;
+
 impl Cart {
     // Create a function that we expect to collide!
     fn builder() -> Self {
@@ -20,12 +35,47 @@ impl Cart {
     }
 }
 
+#[derive(Debug, BetterBuilder)]
+#[builder(build_fn = "finish", unordered, state_prefix = "ShipmentNeeds")]
+struct Shipment {
+    #[builder(state_alias = "ShipmentWithoutFrom")]
+    from: String,
+    #[builder(state_alias = "ShipmentWithoutTo")]
+    to: String,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(build_fn = "finish")]
+enum Message {
+    #[builder(name = "PingFactory", constructor = "ping")]
+    Ping,
+    Text {
+        body: String,
+    },
+}
+
 fn main() {
-    let t = Cart::builder()
-        .owner("Alice".to_string())
-        .num_wheels(4)
-        .build();
+    let _ = (CartBuilder, CartBuilderMissingOwner, CartNeedsOwner);
+
+    let t = Cart::builder();
+    assert_eq!(t.owner, "".to_string());
+
+    let first: CartWithoutOwner = Cart::factory();
+    let t: CartFactory = first.owner("Alice".to_string()).num_wheels(4);
+    let t = t.finish();
     assert_eq!(t.owner, "Alice".to_string());
     assert_eq!(t.num_wheels, 4);
     assert_eq!(t.num_seats, None);
+
+    let shipment = Shipment::builder()
+        .to("Berlin".to_string())
+        .from("Paris".to_string())
+        .finish();
+    assert_eq!(shipment.from, "Paris");
+    let _: ShipmentBuilder<ShipmentWithoutFrom, ShipmentWithoutTo> = Shipment::builder();
+
+    let ping: PingFactory = Message::ping();
+    assert_eq!(ping.finish(), Message::Ping);
+    let text = Message::text_builder().body("Hello".to_string()).finish();
+    assert_eq!(text, Message::Text { body: "Hello".to_string() });
 }
@@ -0,0 +1,186 @@
+//! This test checks builders generated for the arguments of functions and methods.
+#![deny(missing_docs)]
+
+use std::{borrow::Cow, collections::HashMap, fmt::Display, future::Future, time::Duration};
+
+/// Connects to a host.
+#[better_builder::builder]
+pub fn connect(
+    /// The host to connect to.
+    host: String,
+    #[builder(default = 443)] port: u16,
+    timeout: Option<Duration>,
+) -> String {
+    format!("{host}:{port} ({timeout:?})")
+}
+
+/// Finds the first word of `text`, borrowing from it.
+#[better_builder::builder(unordered, into)]
+fn first_word<'t>(text: &'t str, separator: Cow<'_, str>, #[builder(skip)] skipped: u8) -> &'t str {
+    assert_eq!(skipped, 0);
+    text.split(&*separator).next().unwrap_or_default()
+}
+
+/// Returns the only lifetime of its arguments.
+#[better_builder::builder]
+fn trim(text: &str) -> &str {
+    text.trim()
+}
+
+/// Formats a value a number of times.
+#[better_builder::builder(build_fn = "run", constructor = "repeat_with")]
+fn repeat<T: Display, const N: usize>(value: T) -> String
+where
+    T: Clone,
+{
+    std::iter::repeat(value.to_string()).take(N).collect()
+}
+
+/// Parses a value of a type which only appears in the output.
+#[better_builder::builder]
+fn parse<T: std::str::FromStr>(text: &str) -> Option<T> {
+    text.parse().ok()
+}
+
+/// Doubles a value asynchronously.
+#[better_builder::builder]
+async fn double(value: u32, #[builder(each = "label")] labels: Vec<&str>) -> u32 {
+    value * 2 + labels.len() as u32
+}
+
+/// Reads from a raw pointer.
+#[better_builder::builder]
+unsafe fn read(pointer: *const u8) -> u8 {
+    *pointer
+}
+
+/// Counts the even numbers up to a limit, returning an `impl Trait`.
+#[better_builder::builder]
+fn evens(limit: u8) -> impl Iterator<Item = u8> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
+
+/// A client holding a cache.
+pub struct Client<K> {
+    name: String,
+    cache: HashMap<K, String>,
+}
+
+#[better_builder::builder]
+impl<K: std::hash::Hash + Eq> Client<K> {
+    const SUFFIX: &'static str = "?";
+
+    /// Creates a client.
+    #[builder]
+    pub fn new(name: String, #[builder(skip)] cache: HashMap<K, String>) -> Self {
+        Self { name, cache }
+    }
+
+    /// Stores a value in the cache.
+    #[builder(strip_option)]
+    pub fn insert(&mut self, key: K, value: String, prefix: Option<&str>) -> Option<String> {
+        let value = format!("{}{value}", prefix.unwrap_or_default());
+        self.cache.insert(key, value)
+    }
+
+    /// Looks up a value in the cache.
+    #[builder]
+    pub fn get(&self, key: &K) -> Option<&str> {
+        self.cache.get(key).map(String::as_str)
+    }
+
+    /// Shortens the name, with an explicitly typed receiver taking part in lifetime elision.
+    #[builder]
+    pub fn short_name(self: &Self, length: usize, separator: &str) -> &str {
+        self.name[..length].trim_end_matches(separator)
+    }
+
+    /// Renames the client.
+    #[builder(unordered)]
+    pub fn rename(self, name: String, #[builder(default = Self::SUFFIX)] suffix: &str) -> Self {
+        Self {
+            name: format!("{name}{suffix}"),
+            ..self
+        }
+    }
+
+    /// Not every method has a builder.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Describes the client asynchronously.
+    #[builder]
+    pub async fn describe(&self, verbose: bool) -> String {
+        if verbose {
+            format!("{} with {} entries", self.name, self.cache.len())
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    use std::task::{RawWaker, RawWakerVTable, Waker};
+
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(std::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    // SAFETY: The waker does nothing, so it never uses its data.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+    let mut context = std::task::Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn main() {
+    let address = connect_builder()
+        .host("localhost".to_string())
+        .timeout(Some(Duration::from_secs(1)))
+        .call();
+    assert_eq!(address, "localhost:443 (Some(1s))");
+    assert_eq!(connect("a".to_string(), 1, None), "a:1 (None)");
+
+    let text = String::from("hello world");
+    let word = first_word_builder().separator(" ").text(text.as_str()).call();
+    assert_eq!(word, "hello");
+    assert_eq!(trim_builder().text("  padded ").call(), "padded");
+
+    assert_eq!(repeat_with::<_, 3>().value(1).run(), "111");
+    assert_eq!(parse_builder::<u8>().text("7").call(), Some(7));
+
+    let future = double_builder().value(4).label("a").label("b").call();
+    assert_eq!(block_on(future), 10);
+
+    let byte = 5;
+    assert_eq!(unsafe { read_builder().pointer(&byte).call() }, 5);
+
+    assert_eq!(evens_builder().limit(7).call().collect::<Vec<_>>(), [0, 2, 4, 6]);
+
+    let mut client = Client::<u8>::new_builder().name("main".to_string()).call();
+    client
+        .insert_builder()
+        .key(1)
+        .value("one".to_string())
+        .prefix("#")
+        .call();
+    assert_eq!(client.get_builder().key(&1).call(), Some("#one"));
+    assert_eq!(block_on(client.describe_builder().verbose(true).call()), "main with 1 entries");
+
+    let separator = String::from("-");
+    let short_name = client.short_name_builder().length(2).separator(&separator).call();
+    drop(separator);
+    assert_eq!(short_name, "ma");
+
+    let client = client.rename_builder().suffix("!").name("other".to_string()).call();
+    assert_eq!(client.name(), "other!");
+    let client = client.rename_builder().name("last".to_string()).call();
+    assert_eq!(client.name(), "last?");
+}
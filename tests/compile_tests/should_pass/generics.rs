@@ -1,5 +1,7 @@
 //! This test checks that the macro works with various levels of generics.
 
+use std::fmt::Debug;
+
 use better_builder::BetterBuilder;
 
 #[derive(Debug, BetterBuilder)]
@@ -9,6 +11,36 @@ struct Cart<T: AsRef<str>> {
     num_seats: Option<u8>,
 }
 
+#[derive(Debug, BetterBuilder)]
+struct Borrowed<'a, 'b: 'a> {
+    owner: &'a str,
+    inventory: &'b [String],
+    nickname: Option<&'a str>,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Bounded<K, V>
+where
+    K: Debug + Clone,
+    V: Default,
+{
+    key: K,
+    value: V,
+    fallback: Option<V>,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Tray<'a, T: Copy, const N: usize> {
+    slots: [T; N],
+    label: &'a str,
+    spare: Option<T>,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Unused<T> {
+    maybe: Option<T>,
+}
+
 fn main() {
     let t = Cart::builder()
         .owner("Alice".to_string())
@@ -17,4 +49,36 @@ fn main() {
     assert_eq!(t.owner, "Alice".to_string());
     assert_eq!(t.num_wheels, 4);
     assert_eq!(t.num_seats, None);
+
+    let t = Cart::builder().owner("Bob").num_wheels(3).num_seats(Some(1)).build();
+    assert_eq!(t.owner, "Bob");
+    assert_eq!(t.num_seats, Some(1));
+
+    let inventory = vec!["apple".to_string()];
+    let owner = String::from("Alice");
+    let t = Borrowed::builder()
+        .owner(&owner)
+        .inventory(&inventory)
+        .nickname(Some("Al"))
+        .build();
+    assert_eq!(t.owner, "Alice");
+    assert_eq!(t.inventory, &["apple".to_string()]);
+    assert_eq!(t.nickname, Some("Al"));
+
+    let t = Bounded::builder().key("key").value(5u32).build();
+    assert_eq!(t.key, "key");
+    assert_eq!(t.value, 5);
+    assert_eq!(t.fallback, None);
+
+    let t = Tray::builder()
+        .slots([1u8, 2, 3])
+        .label("tray")
+        .spare(Some(4))
+        .build();
+    assert_eq!(t.slots, [1, 2, 3]);
+    assert_eq!(t.label, "tray");
+    assert_eq!(t.spare, Some(4));
+
+    let t = Unused::<u8>::builder().build();
+    assert_eq!(t.maybe, None);
 }
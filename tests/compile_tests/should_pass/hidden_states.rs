@@ -0,0 +1,80 @@
+//! This test checks that intermediate states are hidden, unless an alias is requested.
+
+use better_builder::BetterBuilder;
+
+mod shop {
+    use std::collections::HashMap;
+
+    use better_builder::BetterBuilder;
+
+    pub struct Price(pub u32);
+
+    #[derive(Debug, BetterBuilder)]
+    pub struct Cart {
+        #[builder(state_alias = "CartWithoutOwner")]
+        pub owner: String,
+        #[builder(state_alias = "CartWithoutPrices")]
+        pub prices: HashMap<String, u32>,
+        pub discount: Option<u8>,
+    }
+
+    #[derive(BetterBuilder)]
+    pub(crate) struct Order<'a, T: Clone> {
+        pub item: &'a T,
+        pub total: Price,
+    }
+
+    #[derive(BetterBuilder)]
+    pub(super) struct Receipt {
+        pub total: Price,
+    }
+
+    mod inner {
+        use better_builder::BetterBuilder;
+
+        #[derive(BetterBuilder)]
+        pub(in super::super) struct Note {
+            pub text: String,
+        }
+
+        pub(in super::super) fn note() -> Note {
+            Note::builder().text("thanks".to_string()).build()
+        }
+    }
+
+    pub(crate) fn note_text() -> String {
+        inner::note().text
+    }
+}
+
+// A type sharing the name of an intermediate state no longer collides.
+#[allow(dead_code)]
+struct CartBuilderMissingOwner;
+
+#[derive(BetterBuilder)]
+struct Local {
+    value: u8,
+}
+
+struct Holder {
+    half_built: shop::CartWithoutPrices,
+}
+
+fn main() {
+    let start: shop::CartWithoutOwner = shop::Cart::builder();
+    let holder = Holder {
+        half_built: start.owner("Alice".to_string()),
+    };
+    let cart = holder.half_built.prices(Default::default()).build();
+    assert_eq!(cart.owner, "Alice");
+
+    let order = shop::Order::builder().item(&1).total(shop::Price(3)).build();
+    assert_eq!(*order.item, 1);
+    assert_eq!(order.total.0, 3);
+
+    let receipt = shop::Receipt::builder().total(shop::Price(4)).build();
+    assert_eq!(receipt.total.0, 4);
+    assert_eq!(shop::note_text(), "thanks");
+
+    assert_eq!(Local::builder().value(1).build().value, 1);
+}
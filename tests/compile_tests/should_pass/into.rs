@@ -0,0 +1,64 @@
+//! This test checks that `#[builder(into)]` setters accept any type which converts into the field's
+//! type.
+
+use std::path::PathBuf;
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart {
+    #[builder(into)]
+    owner: String,
+    num_wheels: u8,
+    #[builder(into)]
+    nickname: Option<Box<str>>,
+    #[builder(into, default)]
+    home: PathBuf,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(into)]
+struct Garage<T> {
+    owner: String,
+    // Numeric fields keep their plain setters so literals still infer.
+    capacity: u32,
+    // As do fields using a generic parameter.
+    contents: Vec<T>,
+    location: Option<PathBuf>,
+    #[builder(default)]
+    rating: f32,
+}
+
+fn main() {
+    let t = Cart::builder()
+        .owner("Alice")
+        .num_wheels(4)
+        .nickname(Some("Al".into()))
+        .home("/home/alice")
+        .build();
+    assert_eq!(t.owner, "Alice");
+    assert_eq!(t.num_wheels, 4);
+    assert_eq!(t.nickname.as_deref(), Some("Al"));
+    assert_eq!(t.home, PathBuf::from("/home/alice"));
+
+    let t = Cart::builder()
+        .owner(String::from("Bob"))
+        .num_wheels(3)
+        .home(PathBuf::from("/home/bob"))
+        .build();
+    assert_eq!(t.owner, "Bob");
+    assert_eq!(t.nickname, None);
+
+    let t = Garage::builder()
+        .owner("Alice")
+        .capacity(2)
+        .contents(vec![1, 2, 3])
+        .location(PathBuf::from("/garage"))
+        .rating(4.5)
+        .build();
+    assert_eq!(t.owner, "Alice");
+    assert_eq!(t.capacity, 2);
+    assert_eq!(t.contents, vec![1, 2, 3]);
+    assert_eq!(t.location, Some(PathBuf::from("/garage")));
+    assert_eq!(t.rating, 4.5);
+}
@@ -0,0 +1,70 @@
+//! This test checks that builders can be derived on structs declared inside a function body, and
+//! on structs whose fields use paths relative to their module.
+
+use better_builder::BetterBuilder;
+
+pub struct Owner(pub String);
+
+mod shop {
+    use better_builder::BetterBuilder;
+
+    #[derive(BetterBuilder)]
+    pub struct Cart {
+        pub owner: super::Owner,
+        pub total: self::Price,
+        pub discount: Option<u8>,
+    }
+
+    pub struct Price(pub u32);
+
+    #[derive(BetterBuilder)]
+    #[builder(unordered)]
+    pub struct Order {
+        pub owner: super::Owner,
+        pub price: Price,
+    }
+}
+
+fn main() {
+    struct Wheel(u8);
+
+    #[derive(BetterBuilder)]
+    struct Cart {
+        owner: String,
+        wheel: Wheel,
+        discount: Option<u8>,
+    }
+
+    #[derive(BetterBuilder)]
+    #[builder(unordered)]
+    struct Order {
+        wheel: Wheel,
+        quantity: u8,
+    }
+
+    let cart = Cart::builder()
+        .owner("Alice".to_string())
+        .wheel(Wheel(4))
+        .build();
+    assert_eq!(cart.owner, "Alice");
+    assert_eq!(cart.wheel.0, 4);
+    assert_eq!(cart.discount, None);
+
+    let order = Order::builder().quantity(2).wheel(Wheel(3)).build();
+    assert_eq!(order.quantity, 2);
+    assert_eq!(order.wheel.0, 3);
+
+    let cart = shop::Cart::builder()
+        .owner(Owner("Bob".to_string()))
+        .total(shop::Price(5))
+        .build();
+    assert_eq!(cart.owner.0, "Bob");
+    assert_eq!(cart.total.0, 5);
+
+    let order = shop::Order::builder()
+        .price(shop::Price(1))
+        .owner(Owner("Carol".to_string()))
+        .build();
+    assert_eq!(order.owner.0, "Carol");
+    assert_eq!(order.price.0, 1);
+}
@@ -0,0 +1,129 @@
+use better_builder::BetterBuilder;
+
+mod parts {
+    use better_builder::BetterBuilder;
+
+    #[derive(Debug, PartialEq, BetterBuilder)]
+    pub enum Fuel {
+        Petrol,
+        Diesel,
+    }
+
+    #[derive(Debug, PartialEq, BetterBuilder)]
+    pub struct Engine {
+        pub cylinders: u8,
+        pub fuel: Fuel,
+        pub turbo: Option<bool>,
+    }
+
+    #[derive(Debug, PartialEq, BetterBuilder)]
+    #[builder(unordered)]
+    pub struct Wheel<T> {
+        pub size: T,
+        pub pressure: u8,
+    }
+}
+
+use parts::{Engine, Fuel};
+
+#[derive(Debug, BetterBuilder)]
+#[builder(constructor = "factory", build_fn = "finish")]
+struct Seat {
+    material: String,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Mirror {
+    #[builder(validate = |angle: &u8| if *angle < 90 { Ok(()) } else { Err("too wide") })]
+    angle: u8,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Van {
+    #[builder(nested)]
+    seat: Seat,
+    #[builder(nested)]
+    mirror: Mirror,
+    #[builder(nested, strip_option)]
+    spare_mirror: Option<Mirror>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered)]
+struct Truck {
+    #[builder(nested)]
+    mirror: Mirror,
+    #[builder(nested)]
+    seat: Seat,
+}
+
+fn build_van(angle: u8) -> Result<Van, MirrorBuilderError> {
+    let van = Van::builder()
+        .seat(|s| s.material("leather".to_string()))
+        .mirror(|m| m.angle(angle))?
+        .spare_mirror(|m| m.angle(30))?
+        .build();
+    Ok(van)
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Paint {
+    colour: Option<String>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(strip_option)]
+struct Car {
+    #[builder(nested)]
+    engine: parts::Engine,
+    #[builder(nested)]
+    wheel: parts::Wheel<u16>,
+    #[builder(nested)]
+    spare: Option<parts::Wheel<u16>>,
+    #[builder(nested)]
+    paint: Paint,
+}
+
+fn main() {
+    let car = Car::builder()
+        .engine(|e| e.cylinders(4).fuel(Fuel::Petrol))
+        .wheel(|w| w.pressure(32).size(17))
+        .paint(|p| p)
+        .build();
+    assert_eq!(
+        car.engine,
+        Engine {
+            cylinders: 4,
+            fuel: Fuel::Petrol,
+            turbo: None,
+        }
+    );
+    assert_eq!(car.wheel.size, 17);
+    assert_eq!(car.spare, None);
+    assert_eq!(car.paint.colour, None);
+
+    let car = Car::builder()
+        .engine(|e| e.cylinders(6).fuel(Fuel::Diesel).turbo(Some(true)))
+        .wheel(|w| w.size(16).pressure(30))
+        .paint(|p| p.colour(Some("red".to_string())))
+        .spare(|w| w.size(15).pressure(28))
+        .build();
+    assert_eq!(car.engine.turbo, Some(true));
+    assert_eq!(car.spare.map(|spare| spare.size), Some(15));
+    assert_eq!(car.paint.colour.as_deref(), Some("red"));
+
+    // Inner types may rename their entry point and build method, or be validated.
+    let van = build_van(45).unwrap();
+    assert_eq!(van.seat.material, "leather");
+    assert_eq!(van.mirror.angle, 45);
+    assert_eq!(van.spare_mirror.map(|mirror| mirror.angle), Some(30));
+    assert!(build_van(120).is_err());
+
+    let truck = Truck::builder()
+        .seat(|s| s.material("cloth".to_string()))
+        .mirror(|m| m.angle(10))
+        .unwrap()
+        .build();
+    assert_eq!(truck.mirror.angle, 10);
+    assert_eq!(truck.seat.material, "cloth");
+}
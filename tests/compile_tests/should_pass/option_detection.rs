@@ -0,0 +1,67 @@
+//! This test checks that fully qualified `Option` paths are detected as optional, and that
+//! `#[builder(required)]` and `#[builder(optional)]` override the detection.
+
+use better_builder::BetterBuilder;
+
+type MaybeU8 = Option<u8>;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart {
+    owner: String,
+    num_seats: std::option::Option<u8>,
+    num_wheels: ::core::option::Option<u8>,
+    #[builder(required)]
+    nickname: Option<String>,
+    #[builder(optional)]
+    num_doors: MaybeU8,
+    #[builder(required, strip_option)]
+    colour: Option<String>,
+}
+
+mod local {
+    /// A type which happens to be named `Option`, but is not [`std::option::Option`].
+    #[derive(Debug, PartialEq)]
+    pub struct Option<T>(pub T);
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Garage {
+    #[builder(required)]
+    owner: local::Option<String>,
+    capacity: local::Option<u8>,
+}
+
+fn main() {
+    let t = Cart::builder()
+        .owner("Alice".to_string())
+        .nickname(None)
+        .colour("red".to_string())
+        .num_doors(Some(2))
+        .build();
+    assert_eq!(t.owner, "Alice");
+    assert_eq!(t.num_seats, None);
+    assert_eq!(t.num_wheels, None);
+    assert_eq!(t.nickname, None);
+    assert_eq!(t.num_doors, Some(2));
+    assert_eq!(t.colour, Some("red".to_string()));
+
+    let t = Cart::builder()
+        .owner("Bob".to_string())
+        .nickname(Some("Bobby".to_string()))
+        .maybe_colour(None)
+        .num_seats(Some(4))
+        .num_wheels(Some(3))
+        .build();
+    assert_eq!(t.nickname, Some("Bobby".to_string()));
+    assert_eq!(t.num_seats, Some(4));
+    assert_eq!(t.num_wheels, Some(3));
+    assert_eq!(t.num_doors, None);
+    assert_eq!(t.colour, None);
+
+    let t = Garage::builder()
+        .owner(local::Option("Alice".to_string()))
+        .capacity(local::Option(2))
+        .build();
+    assert_eq!(t.owner, local::Option("Alice".to_string()));
+    assert_eq!(t.capacity, local::Option(2));
+}
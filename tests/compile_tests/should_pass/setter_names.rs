@@ -0,0 +1,66 @@
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart {
+    #[builder(setter(name = "owned_by"))]
+    owner: String,
+    r#type: String,
+    #[builder(setter(name = "enabled"))]
+    is_enabled: bool,
+    #[builder(setter(name = "note"), strip_option)]
+    comment: Option<String>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(setter_prefix = "with_")]
+struct Order {
+    owner: String,
+    #[builder(default)]
+    quantity: u32,
+    #[builder(setter(name = "discounted"))]
+    discount: Option<u8>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(setter_prefix = "set_", unordered)]
+struct Shipment {
+    from: String,
+    to: String,
+}
+
+fn main() {
+    let cart = Cart::builder()
+        .owned_by("Alice".to_string())
+        .r#type("grocery".to_string())
+        .enabled(true)
+        .note("fragile".to_string())
+        .build();
+    assert_eq!(cart.owner, "Alice");
+    assert_eq!(cart.r#type, "grocery");
+    assert!(cart.is_enabled);
+    assert_eq!(cart.comment.as_deref(), Some("fragile"));
+
+    let cart = Cart::builder()
+        .owned_by("Bob".to_string())
+        .r#type("hardware".to_string())
+        .enabled(false)
+        .maybe_note(None)
+        .build();
+    assert_eq!(cart.comment, None);
+
+    let order = Order::builder()
+        .with_owner("Alice".to_string())
+        .with_quantity(2)
+        .discounted(Some(10))
+        .build();
+    assert_eq!(order.owner, "Alice");
+    assert_eq!(order.quantity, 2);
+    assert_eq!(order.discount, Some(10));
+
+    let shipment = Shipment::builder()
+        .set_to("Berlin".to_string())
+        .set_from("Paris".to_string())
+        .build();
+    assert_eq!(shipment.from, "Paris");
+    assert_eq!(shipment.to, "Berlin");
+}
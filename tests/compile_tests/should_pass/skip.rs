@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+use std::time::Instant;
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Request {
+    url: String,
+    #[builder(skip)]
+    attempts: u32,
+    #[builder(skip = Instant::now())]
+    created: Instant,
+    #[builder(skip = vec![0; 4])]
+    buffer: Vec<u8>,
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered)]
+struct Typed<T> {
+    id: u64,
+    marker: PhantomData<T>,
+    #[builder(skip = 7)]
+    version: u8,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Checked {
+    #[builder(validate = |port: &u16| if *port > 0 { Ok(()) } else { Err("zero") })]
+    port: u16,
+    #[builder(skip = "checked".to_string())]
+    label: String,
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Session<T> {
+    user: T,
+    #[builder(skip = Self::FIRST_ID)]
+    id: u64,
+}
+
+impl<T> Session<T> {
+    const FIRST_ID: u64 = 1;
+}
+
+#[derive(Debug, BetterBuilder)]
+struct Wrapper<'a>(&'a str, #[builder(skip)] usize);
+
+#[derive(Debug, BetterBuilder)]
+enum Event {
+    Click {
+        x: i32,
+        #[builder(skip = 1)]
+        count: u8,
+    },
+}
+
+fn main() {
+    let request = Request::builder()
+        .url("https://example.com".to_string())
+        .timeout(Some(5))
+        .build();
+    assert_eq!(request.attempts, 0);
+    assert_eq!(request.buffer, vec![0; 4]);
+    assert!(request.created <= Instant::now());
+
+    // Skipped fields are initialised again when the struct is rebuilt.
+    let rebuilt = Request {
+        attempts: 3,
+        ..request
+    }
+    .to_builder()
+    .build();
+    assert_eq!(rebuilt.attempts, 0);
+    assert_eq!(rebuilt.url, "https://example.com");
+
+    let typed = Typed::<String>::builder().id(1).build();
+    assert_eq!(typed.id, 1);
+    assert_eq!(typed.version, 7);
+    let _: PhantomData<String> = typed.marker;
+
+    let checked = Checked::builder().port(80).try_build().unwrap();
+    assert_eq!(checked.label, "checked");
+
+    let session = Session::builder().user("alice").build();
+    assert_eq!((session.user, session.id), ("alice", 1));
+
+    let wrapper = Wrapper::builder()._0("value").build();
+    assert_eq!(wrapper.0, "value");
+    assert_eq!(wrapper.1, 0);
+
+    let Event::Click { x, count } = Event::click_builder().x(3).build();
+    assert_eq!((x, count), (3, 1));
+}
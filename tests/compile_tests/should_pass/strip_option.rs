@@ -0,0 +1,59 @@
+//! This test checks that `#[builder(strip_option)]` setters accept the inner type of an `Option`,
+//! with a `maybe_` setter accepting the `Option` itself.
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart {
+    owner: String,
+    #[builder(strip_option)]
+    num_seats: Option<u8>,
+    #[builder(strip_option, into)]
+    nickname: Option<String>,
+    #[builder(strip_option, default = Some(4))]
+    num_wheels: Option<u8>,
+    colour: Option<String>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(strip_option)]
+struct Garage<T> {
+    capacity: u32,
+    contents: Option<Vec<T>>,
+    #[builder(default)]
+    open: bool,
+}
+
+fn main() {
+    let t = Cart::builder()
+        .owner("Alice".to_string())
+        .num_seats(2)
+        .nickname("Al")
+        .num_wheels(3)
+        .build();
+    assert_eq!(t.owner, "Alice");
+    assert_eq!(t.num_seats, Some(2));
+    assert_eq!(t.nickname, Some("Al".to_string()));
+    assert_eq!(t.num_wheels, Some(3));
+    assert_eq!(t.colour, None);
+
+    let existing = None;
+    let t = Cart::builder()
+        .owner("Bob".to_string())
+        .maybe_num_seats(existing)
+        .maybe_nickname(Some("Bobby".to_string()))
+        .maybe_num_wheels(None)
+        .colour(Some("red".to_string()))
+        .build();
+    assert_eq!(t.num_seats, None);
+    assert_eq!(t.nickname, Some("Bobby".to_string()));
+    assert_eq!(t.num_wheels, None);
+    assert_eq!(t.colour, Some("red".to_string()));
+
+    let t = Cart::builder().owner("Carol".to_string()).build();
+    assert_eq!(t.num_wheels, Some(4));
+
+    let t = Garage::builder().capacity(2).contents(vec!["bike"]).build();
+    assert_eq!(t.contents, Some(vec!["bike"]));
+    assert!(!t.open);
+}
@@ -0,0 +1,54 @@
+use better_builder::BetterBuilder;
+
+#[derive(Debug, Clone, PartialEq, BetterBuilder)]
+struct Fixture {
+    name: String,
+    retries: u8,
+    timeout: Option<u32>,
+    #[builder(default = 8080)]
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(unordered)]
+struct Pair<'a, T> {
+    left: &'a T,
+    right: &'a T,
+    label: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+struct Wrapper(u8, #[builder(default)] String);
+
+fn main() {
+    let baseline = Fixture::builder()
+        .name("baseline".to_string())
+        .retries(3)
+        .build();
+
+    let variant = baseline
+        .clone()
+        .to_builder()
+        .timeout(Some(10))
+        .port(9090)
+        .build();
+    assert_eq!(variant.name, "baseline");
+    assert_eq!(variant.retries, 3);
+    assert_eq!(variant.timeout, Some(10));
+    assert_eq!(variant.port, 9090);
+
+    let unchanged = FixtureBuilder::from(baseline.clone()).build();
+    assert_eq!(unchanged, baseline);
+
+    let builder: FixtureBuilder = baseline.clone().into();
+    assert_eq!(builder.timeout(None).build().port, 8080);
+
+    let (one, two) = (1, 2);
+    let pair = Pair::builder().left(&one).right(&two).build();
+    let relabelled = pair.to_builder().label(Some("numbers")).build();
+    assert_eq!(relabelled.left, &1);
+    assert_eq!(relabelled.label, Some("numbers"));
+
+    let wrapper = Wrapper::builder()._0(1).build().to_builder()._1("one".to_string()).build();
+    assert_eq!(wrapper, Wrapper(1, "one".to_string()));
+}
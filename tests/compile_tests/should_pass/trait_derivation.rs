@@ -1 +1,52 @@
 //! This test checks that derives are automatically propagated to the builder.
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(derive(Clone, Debug, PartialEq), attr(must_use = "builders do nothing unless built"))]
+struct Cart {
+    owner: String,
+    num_wheels: u8,
+    num_seats: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(unordered, derive(Clone, Debug))]
+struct Pair<T> {
+    left: T,
+    right: T,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(derive(Clone))]
+enum Message {
+    Text { body: String, urgent: bool },
+}
+
+fn main() {
+    let half_built = Cart::builder().owner("Alice".to_string());
+    let copy = half_built.clone();
+    assert_eq!(format!("{half_built:?}"), format!("{copy:?}"));
+
+    let car = half_built.clone().num_wheels(4).build();
+    let bike = half_built.num_wheels(2).num_seats(Some(1)).build();
+    assert_eq!(car.owner, "Alice");
+    assert_eq!(car.num_wheels, 4);
+    assert_eq!(bike.num_wheels, 2);
+    assert_eq!(bike.num_seats, Some(1));
+
+    let complete = copy.num_wheels(3);
+    assert_eq!(complete.clone(), complete);
+
+    let half_pair = Pair::builder().left(1);
+    let first = half_pair.clone().right(2).build();
+    let second = half_pair.right(3).build();
+    assert_eq!((first.left, first.right), (1, 2));
+    assert_eq!((second.left, second.right), (1, 3));
+    let _ = format!("{:?}", Pair::<u8>::builder());
+
+    let text = Message::text_builder().body("Hello".to_string());
+    let calm = text.clone().urgent(false).build();
+    let urgent = text.urgent(true).build();
+    assert_ne!(calm, urgent);
+}
@@ -0,0 +1,106 @@
+//! This test checks that generated builders implement `Buildable` and `CompleteBuilder`, so they
+//! can be used by generic code.
+
+use better_builder::{BetterBuilder, Buildable, CompleteBuilder};
+
+mod shapes {
+    use better_builder::BetterBuilder;
+
+    #[derive(Debug, PartialEq, BetterBuilder)]
+    pub struct Square<T> {
+        pub side: T,
+    }
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+struct Cart {
+    owner: String,
+    num_wheels: u8,
+    discount: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(unordered)]
+struct Wheel {
+    radius: u8,
+    colour: Option<String>,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+#[builder(build_fn = "finish")]
+struct Discount {
+    #[builder(validate = |percent: &u8| if *percent <= 100 { Ok(()) } else { Err("too large") })]
+    percent: u8,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+enum Shape {
+    Circle { radius: u8 },
+}
+
+#[derive(Debug, PartialEq)]
+struct Connection {
+    address: String,
+}
+
+#[better_builder::builder]
+impl Connection {
+    #[better_builder::constructor]
+    fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+        }
+    }
+}
+
+#[better_builder::builder]
+fn area(width: u8, height: u8) -> u16 {
+    u16::from(width) * u16::from(height)
+}
+
+/// Builds a value whose builder has no required fields.
+fn build_default<T>() -> <T::Builder as CompleteBuilder>::Output
+where
+    T: Buildable,
+    T::Builder: CompleteBuilder,
+{
+    T::builder().build()
+}
+
+/// Builds every complete builder, such as a set of test fixtures.
+fn build_all<B: CompleteBuilder>(builders: Vec<B>) -> Vec<B::Output> {
+    builders.into_iter().map(CompleteBuilder::build).collect()
+}
+
+fn main() {
+    let carts = build_all(vec![
+        Cart::builder().owner("Alice".to_string()).num_wheels(4),
+        <Cart as Buildable>::builder()
+            .owner("Bob".to_string())
+            .num_wheels(3)
+            .discount(Some(10)),
+    ]);
+    assert_eq!(carts[1].discount, Some(10));
+
+    let wheel = Wheel::builder().radius(10);
+    assert_eq!(CompleteBuilder::build(wheel).radius, 10);
+
+    let square = <shapes::Square<u8> as Buildable>::builder().side(2).build();
+    assert_eq!(square.side, 2);
+
+    let discount = CompleteBuilder::build(Discount::builder().percent(150));
+    assert!(discount.is_err());
+
+    let shape = CompleteBuilder::build(Shape::circle_builder().radius(1));
+    assert_eq!(shape, Shape::Circle { radius: 1 });
+
+    let connection = CompleteBuilder::build(Connection::builder().address("localhost"));
+    assert_eq!(connection.address, "localhost");
+    assert_eq!(CompleteBuilder::build(area_builder().width(2).height(3)), 6);
+
+    #[derive(Debug, PartialEq, BetterBuilder)]
+    struct Settings {
+        verbose: Option<bool>,
+    }
+    assert_eq!(build_default::<Settings>(), Settings { verbose: None });
+}
@@ -0,0 +1,53 @@
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+struct Cart(
+    String,
+    u8,
+    Option<u8>,
+    Vec<String>,
+);
+
+#[derive(Debug, BetterBuilder)]
+struct Order(
+    #[builder(name = "owner")] String,
+    #[builder(name = "quantity", default = 1)] u32,
+    Option<String>,
+);
+
+#[derive(Debug, BetterBuilder)]
+struct UserId(u64);
+
+#[derive(Debug, BetterBuilder)]
+struct Unit;
+
+fn main() {
+    let cart = Cart::builder()
+        ._0("Alice".to_string())
+        ._1(4)
+        ._3(vec!["apple".to_string()])
+        ._2(Some(2))
+        .build();
+    assert_eq!(cart.0, "Alice");
+    assert_eq!(cart.1, 4);
+    assert_eq!(cart.2, Some(2));
+    assert_eq!(cart.3, vec!["apple".to_string()]);
+
+    let order = Order::builder().owner("Bob".to_string()).build();
+    assert_eq!(order.0, "Bob");
+    assert_eq!(order.1, 1);
+    assert_eq!(order.2, None);
+
+    let order = Order::builder()
+        .owner("Bob".to_string())
+        .quantity(3)
+        ._2(Some("note".to_string()))
+        .build();
+    assert_eq!(order.1, 3);
+    assert_eq!(order.2.as_deref(), Some("note"));
+
+    let id = UserId::builder()._0(42).build();
+    assert_eq!(id.0, 42);
+
+    let _unit: Unit = Unit::builder().build();
+}
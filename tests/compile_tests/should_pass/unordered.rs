@@ -0,0 +1,64 @@
+//! This test checks that required fields may be set in any order with `#[builder(unordered)]`.
+
+use better_builder::BetterBuilder;
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered)]
+struct Cart {
+    owner: String,
+    num_wheels: u8,
+    num_seats: Option<u8>,
+    #[builder(default = 1)]
+    num_doors: u8,
+    inventory: Vec<String>,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered, strip_option, into)]
+struct Tray<'a, T: Copy = u8, const N: usize = 2> {
+    slots: [T; N],
+    label: &'a str,
+    spare: Option<T>,
+    name: String,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered)]
+struct Empty {}
+
+fn main() {
+    let t = Cart::builder()
+        .inventory(vec!["apple".to_string()])
+        .num_seats(Some(2))
+        .num_wheels(4)
+        .owner("Alice".to_string())
+        .build();
+    assert_eq!(t.owner, "Alice");
+    assert_eq!(t.num_wheels, 4);
+    assert_eq!(t.num_seats, Some(2));
+    assert_eq!(t.num_doors, 1);
+    assert_eq!(t.inventory, vec!["apple".to_string()]);
+
+    let t = Cart::builder()
+        .owner("Bob".to_string())
+        .num_doors(3)
+        .inventory(Vec::new())
+        .num_wheels(3)
+        .build();
+    assert_eq!(t.owner, "Bob");
+    assert_eq!(t.num_seats, None);
+    assert_eq!(t.num_doors, 3);
+
+    let t = Tray::builder()
+        .name("tray")
+        .label("label")
+        .spare(7u8)
+        .slots([1, 2, 3])
+        .build();
+    assert_eq!(t.slots, [1, 2, 3]);
+    assert_eq!(t.label, "label");
+    assert_eq!(t.spare, Some(7));
+    assert_eq!(t.name, "tray");
+
+    let _t = Empty::builder().build();
+}
@@ -0,0 +1,142 @@
+use better_builder::BetterBuilder;
+
+fn valid_port(port: &u16) -> Result<(), String> {
+    if *port >= 1024 {
+        Ok(())
+    } else {
+        Err(format!("port {port} is reserved"))
+    }
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(validate = ServiceConfig::validate)]
+struct ServiceConfig {
+    host: String,
+    #[builder(validate = valid_port)]
+    port: u16,
+    #[builder(default = 30, validate = |timeout: &u64| if *timeout > 0 { Ok(()) } else { Err("must be positive") })]
+    timeout: u64,
+}
+
+impl ServiceConfig {
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.host.is_empty() {
+            Err("host is empty")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(unordered, build_fn = "finish")]
+struct Range {
+    #[builder(validate = |start: &u8| if *start < 100 { Ok(()) } else { Err("too large") })]
+    start: u8,
+    end: u8,
+}
+
+#[derive(Debug, PartialEq, BetterBuilder)]
+enum Shape {
+    Circle {
+        #[builder(validate = |radius: &f64| if *radius > 0.0 { Ok(()) } else { Err("not positive") })]
+        radius: f64,
+    },
+    Point,
+}
+
+#[derive(Debug, BetterBuilder)]
+#[builder(validate = Self::check)]
+struct Account<T: AsRef<str>> {
+    #[builder(validate = Self::check_name)]
+    name: T,
+    #[builder(validate = |balance: &i64| Self::check_balance(*balance))]
+    balance: i64,
+}
+
+impl<T: AsRef<str>> Account<T> {
+    fn check(&self) -> Result<(), &'static str> {
+        if self.name.as_ref() == "admin" && self.balance != 0 {
+            Err("admin has a balance")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_name(name: &T) -> Result<(), &'static str> {
+        if name.as_ref().is_empty() {
+            Err("empty")
+        } else {
+            Ok(())
+        }
+    }
+
+    const fn check_balance(balance: i64) -> Result<(), &'static str> {
+        if balance < 0 {
+            Err("negative")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    let config = ServiceConfig::builder()
+        .host("localhost".to_string())
+        .port(8080)
+        .try_build()
+        .unwrap();
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.timeout, 30);
+
+    let error = ServiceConfig::builder()
+        .host("localhost".to_string())
+        .port(80)
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, ServiceConfigBuilderError::InvalidPort("port 80 is reserved".to_string()));
+    assert_eq!(error.to_string(), "invalid `port`: port 80 is reserved");
+
+    let error = ServiceConfig::builder()
+        .host("localhost".to_string())
+        .port(8080)
+        .timeout(0)
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error, ServiceConfigBuilderError::InvalidTimeout("must be positive".to_string()));
+
+    let error = ServiceConfig::builder()
+        .host(String::new())
+        .port(8080)
+        .try_build()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "invalid `ServiceConfig`: host is empty");
+    let _: &dyn std::error::Error = &error;
+
+    let range = Range::builder().end(10).start(1).finish().unwrap();
+    assert_eq!((range.start, range.end), (1, 10));
+    assert!(Range::builder().start(200).end(10).finish().is_err());
+
+    let circle = Shape::circle_builder().radius(1.0).try_build();
+    assert_eq!(circle, Ok(Shape::Circle { radius: 1.0 }));
+    assert_eq!(
+        Shape::circle_builder().radius(-1.0).try_build(),
+        Err(ShapeCircleBuilderError::InvalidRadius("not positive".to_string()))
+    );
+    assert_eq!(Shape::point_builder().build(), Shape::Point);
+
+    let account = Account::builder().name("alice").balance(10).try_build().unwrap();
+    assert_eq!((account.name, account.balance), ("alice", 10));
+    assert_eq!(
+        Account::builder().name("").balance(0).try_build().unwrap_err(),
+        AccountBuilderError::InvalidName("empty".to_string())
+    );
+    assert_eq!(
+        Account::builder().name("bob").balance(-1).try_build().unwrap_err(),
+        AccountBuilderError::InvalidBalance("negative".to_string())
+    );
+    assert_eq!(
+        Account::builder().name("admin").balance(1).try_build().unwrap_err(),
+        AccountBuilderError::Invalid("admin has a balance".to_string())
+    );
+}